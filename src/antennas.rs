@@ -1,11 +1,11 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::grid::Location;
+use crate::grid::{self, Location, Point};
 
 #[derive(Debug)]
 pub struct Antennas {
-    height: isize,
-    width: isize,
+    height: usize,
+    width: usize,
     antennas: HashMap<char, Vec<Location>>,
 }
 
@@ -17,8 +17,8 @@ pub enum Antinode {
 
 impl From<&Vec<Vec<char>>> for Antennas {
     fn from(value: &Vec<Vec<char>>) -> Self {
-        let height = value.len() as isize;
-        let width = value[0].len() as isize;
+        let height = value.len();
+        let width = value[0].len();
         let antennas = Antennas::find_antennas(value);
         Self {
             height,
@@ -63,82 +63,29 @@ impl Antennas {
         antennas
     }
 
-    fn is_valid_location(&self, row: isize, col: isize) -> bool {
-        row >= 0 && row < self.height && col >= 0 && col < self.width
+    fn to_location(&self, point: Point) -> Option<Location> {
+        point.to_location(self.height, self.width)
     }
 
     fn calculate_resonant_antinodes(&self, left: Location, right: Location) -> Vec<Location> {
-        let mut result = Vec::new();
-
-        let left_row = left.row as isize;
-        let left_col = left.col as isize;
-
-        let right_row = right.row as isize;
-        let right_col = right.col as isize;
-
-        let row_delta = right_row - left_row;
-        let col_delta = right_col - left_col;
-
-        let first_row = right_row + row_delta;
-        let first_col = right_col + col_delta;
-
-        if self.is_valid_location(first_row, first_col) {
-            result.push(Location {
-                row: first_row as usize,
-                col: first_col as usize,
-            });
-        }
-
-        let second_row = left_row - row_delta;
-        let second_col = left_col - col_delta;
-
-        if self.is_valid_location(second_row, second_col) {
-            result.push(Location {
-                row: second_row as usize,
-                col: second_col as usize,
-            });
-        }
-        result
+        let left = Point::from(left);
+        let right = Point::from(right);
+        let delta = right - left;
+
+        [right + delta, left - delta]
+            .into_iter()
+            .filter_map(|p| self.to_location(p))
+            .collect()
     }
 
     fn calculate_harmonic_antinodes(&self, left: Location, right: Location) -> Vec<Location> {
-        let mut result = Vec::new();
-
-        let left_row = left.row as isize;
-        let left_col = left.col as isize;
-
-        let right_row = right.row as isize;
-        let right_col = right.col as isize;
-
-        let mut row_delta = right_row - left_row;
-        let mut col_delta = right_col - left_col;
-
-        let gcd = num_integer::gcd(row_delta, col_delta);
-        row_delta /= gcd;
-        col_delta /= gcd;
-
-        let mut antinode_row = left_row;
-        let mut antinode_col = left_col;
-        while self.is_valid_location(antinode_row, antinode_col) {
-            result.push(Location {
-                row: antinode_row as usize,
-                col: antinode_col as usize,
-            });
-            antinode_row += row_delta;
-            antinode_col += col_delta;
-        }
-        antinode_row = left_row - row_delta;
-        antinode_col = left_col - col_delta;
-        while self.is_valid_location(antinode_row, antinode_col) {
-            result.push(Location {
-                row: antinode_row as usize,
-                col: antinode_col as usize,
-            });
-            antinode_row -= row_delta;
-            antinode_col -= col_delta;
-        }
+        let left = Point::from(left);
+        let delta = Point::from(right) - left;
+        let delta = delta / num_integer::gcd(delta.x, delta.y);
 
-        result
+        grid::ray(left, delta, self.height, self.width)
+            .chain(grid::ray(left - delta, -delta, self.height, self.width))
+            .collect()
     }
 
     fn calculate_antinodes(
@@ -3,51 +3,349 @@
 //! This module provides reusable breadth-first search implementations
 //! for various grid-based and graph-based problems.
 
-use std::collections::{HashSet, VecDeque};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::hash::Hash;
 
+use crate::grid::{BitGrid, Grid, Location};
+
+/// Tracks which nodes a search has already reached.
+///
+/// Every search here defaults to a `HashSet`; grid searches over [`Location`]s
+/// can pass a [`BitGrid`] to the `_with` variants instead to skip hashing.
+pub trait Visited<N> {
+    /// Mark `node` as visited, returning whether it was new.
+    fn visit(&mut self, node: N) -> bool;
+}
+
+impl<N: Eq + Hash> Visited<N> for HashSet<N> {
+    fn visit(&mut self, node: N) -> bool {
+        self.insert(node)
+    }
+}
+
+impl Visited<Location> for BitGrid {
+    fn visit(&mut self, node: Location) -> bool {
+        self.insert(node)
+    }
+}
+
+/// A route found by a search, from its start node to its goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    nodes: Vec<N>,
+}
+
+impl<N> Path<N> {
+    /// A path through `nodes`, which must include at least the start.
+    pub(crate) fn new(nodes: Vec<N>) -> Self {
+        assert!(!nodes.is_empty(), "a path needs at least its start node");
+        Self { nodes }
+    }
+
+    /// Every node on the path, start and goal included.
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    pub fn into_nodes(self) -> Vec<N> {
+        self.nodes
+    }
+
+    /// Number of steps taken, one less than the number of nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len() - 1
+    }
+
+    /// Whether the start is already the goal, so no steps are taken.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn start(&self) -> &N {
+        &self.nodes[0]
+    }
+
+    pub fn goal(&self) -> &N {
+        &self.nodes[self.nodes.len() - 1]
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, N> {
+        self.nodes.iter()
+    }
+}
+
+impl<N> IntoIterator for Path<N> {
+    type Item = N;
+    type IntoIter = std::vec::IntoIter<N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes.into_iter()
+    }
+}
+
+impl<'a, N> IntoIterator for &'a Path<N> {
+    type Item = &'a N;
+    type IntoIter = std::slice::Iter<'a, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes.iter()
+    }
+}
+
 /// Perform a breadth-first search from a start node to find a goal.
 ///
-/// Returns the path from start to goal if found, or None if unreachable.
+/// Returns a shortest path from start to goal if found, or None if unreachable.
 ///
 /// # Arguments
 /// * `start` - The starting node
 /// * `neighbors` - Function returning neighboring nodes for a given node
 /// * `is_goal` - Predicate to check if a node is the goal
-pub fn search<N, FN, FG>(start: N, neighbors: FN, is_goal: FG) -> Option<Vec<N>>
+pub fn search<N, FN, FG>(start: N, neighbors: FN, is_goal: FG) -> Option<Path<N>>
 where
     N: Eq + Hash + Copy,
     FN: Fn(&N) -> Vec<N>,
     FG: Fn(&N) -> bool,
+{
+    search_with(start, neighbors, is_goal, HashSet::new())
+}
+
+/// Like [`search`], tracking visited nodes in `visited`.
+pub fn search_with<N, FN, FG>(
+    start: N,
+    neighbors: FN,
+    is_goal: FG,
+    mut visited: impl Visited<N>,
+) -> Option<Path<N>>
+where
+    N: Copy,
+    FN: Fn(&N) -> Vec<N>,
+    FG: Fn(&N) -> bool,
 {
     if is_goal(&start) {
-        return Some(vec![start]);
+        return Some(Path { nodes: vec![start] });
     }
 
-    let mut visited: HashSet<N> = HashSet::from([start]);
-    let mut queue: VecDeque<(N, Vec<N>)> = VecDeque::from([(start, vec![start])]);
+    // Each reached node with the index of the node it was reached from, so
+    // the queue only holds indices and the path is rebuilt once at the end.
+    let mut parents: Vec<(N, usize)> = vec![(start, 0)];
+    visited.visit(start);
+    let mut queue: VecDeque<usize> = VecDeque::from([0]);
 
-    while let Some((current, path)) = queue.pop_front() {
+    while let Some(index) = queue.pop_front() {
+        let current = parents[index].0;
         for next in neighbors(&current) {
-            if visited.contains(&next) {
+            if !visited.visit(next) {
                 continue;
             }
 
-            let mut new_path = path.clone();
-            new_path.push(next);
-
+            parents.push((next, index));
             if is_goal(&next) {
-                return Some(new_path);
+                return Some(trace_parents(&parents, parents.len() - 1));
             }
 
-            visited.insert(next);
-            queue.push_back((next, new_path));
+            queue.push_back(parents.len() - 1);
         }
     }
 
     None
 }
 
+/// Find every shortest path from start to any goal.
+///
+/// Returns an empty list if no goal is reachable. Paths that tie share their
+/// common nodes during the search, but the result lists each one in full, so
+/// mazes with many tied routes can still produce a lot of paths.
+///
+/// # Arguments
+/// * `start` - The starting node
+/// * `neighbors` - Function returning neighboring nodes for a given node
+/// * `is_goal` - Predicate to check if a node is the goal
+pub fn search_all_shortest<N, FN, FG>(start: N, neighbors: FN, is_goal: FG) -> Vec<Path<N>>
+where
+    N: Eq + Hash + Copy,
+    FN: Fn(&N) -> Vec<N>,
+    FG: Fn(&N) -> bool,
+{
+    // Every node reached so far, with its distance and all the nodes one step
+    // closer to the start that lead to it.
+    let mut parents: HashMap<N, (usize, Vec<N>)> = HashMap::from([(start, (0, Vec::new()))]);
+    let mut layer = vec![start];
+    let mut goals: Vec<N> = layer.iter().copied().filter(|n| is_goal(n)).collect();
+    let mut dist = 0;
+
+    while goals.is_empty() && !layer.is_empty() {
+        dist += 1;
+        let mut next_layer = Vec::new();
+        for current in layer {
+            for next in neighbors(&current) {
+                match parents.entry(next) {
+                    Entry::Vacant(entry) => {
+                        entry.insert((dist, vec![current]));
+                        next_layer.push(next);
+                        if is_goal(&next) {
+                            goals.push(next);
+                        }
+                    }
+                    Entry::Occupied(mut entry) => {
+                        let (next_dist, next_parents) = entry.get_mut();
+                        if *next_dist == dist && !next_parents.contains(&current) {
+                            next_parents.push(current);
+                        }
+                    }
+                }
+            }
+        }
+        layer = next_layer;
+    }
+
+    let mut result = Vec::new();
+    for goal in goals {
+        let mut partial = vec![goal];
+        collect_paths(&parents, &mut partial, &mut result);
+    }
+    result
+}
+
+/// Search from both ends at once, expanding whichever frontier is smaller
+/// until they meet.
+///
+/// This visits far fewer nodes than [`search`] when the graph branches a lot,
+/// as each side only has to get about halfway. Returns a shortest path from
+/// start to goal, or None if unreachable.
+///
+/// # Arguments
+/// * `start` - The starting node
+/// * `goal` - The node to reach
+/// * `neighbors` - Function returning the nodes reachable in one step from a node
+/// * `reverse_neighbors` - Function returning the nodes that reach a node in one
+///   step; the same as `neighbors` for undirected graphs
+pub fn bidirectional<N, FN, FR>(
+    start: N,
+    goal: N,
+    neighbors: FN,
+    reverse_neighbors: FR,
+) -> Option<Path<N>>
+where
+    N: Eq + Hash + Copy,
+    FN: Fn(&N) -> Vec<N>,
+    FR: Fn(&N) -> Vec<N>,
+{
+    if start == goal {
+        return Some(Path::new(vec![start]));
+    }
+
+    // Each side maps the nodes it reached to their distance and parent.
+    let mut forward: HashMap<N, (usize, N)> = HashMap::from([(start, (0, start))]);
+    let mut backward: HashMap<N, (usize, N)> = HashMap::from([(goal, (0, goal))]);
+    let mut forward_layer = vec![start];
+    let mut backward_layer = vec![goal];
+
+    while !forward_layer.is_empty() && !backward_layer.is_empty() {
+        let meeting = if forward_layer.len() <= backward_layer.len() {
+            expand_layer(&mut forward_layer, &mut forward, &backward, &neighbors)
+        } else {
+            expand_layer(
+                &mut backward_layer,
+                &mut backward,
+                &forward,
+                &reverse_neighbors,
+            )
+        };
+
+        if let Some(meeting) = meeting {
+            let mut nodes = walk_parents(&forward, meeting);
+            nodes.reverse();
+            nodes.extend(walk_parents(&backward, meeting).into_iter().skip(1));
+            return Some(Path::new(nodes));
+        }
+    }
+
+    None
+}
+
+/// Replace `layer` with the nodes one step beyond it, returning the node
+/// closest to both ends if this side has reached the other.
+fn expand_layer<N, FN>(
+    layer: &mut Vec<N>,
+    reached: &mut HashMap<N, (usize, N)>,
+    other: &HashMap<N, (usize, N)>,
+    neighbors: &FN,
+) -> Option<N>
+where
+    N: Eq + Hash + Copy,
+    FN: Fn(&N) -> Vec<N>,
+{
+    let mut next_layer = Vec::new();
+    let mut meeting: Option<(usize, N)> = None;
+    for current in layer.drain(..) {
+        let dist = reached[&current].0 + 1;
+        for next in neighbors(&current) {
+            let Entry::Vacant(entry) = reached.entry(next) else {
+                continue;
+            };
+            entry.insert((dist, current));
+            next_layer.push(next);
+            if let Some(&(other_dist, _)) = other.get(&next) {
+                if meeting.is_none_or(|(best, _)| dist + other_dist < best) {
+                    meeting = Some((dist + other_dist, next));
+                }
+            }
+        }
+    }
+    *layer = next_layer;
+    meeting.map(|(_, node)| node)
+}
+
+/// Nodes from `node` back to the end its side started from.
+fn walk_parents<N: Eq + Hash + Copy>(reached: &HashMap<N, (usize, N)>, node: N) -> Vec<N> {
+    let mut nodes = vec![node];
+    let mut current = node;
+    while let Some(&(dist, parent)) = reached.get(&current) {
+        if dist == 0 {
+            break;
+        }
+        nodes.push(parent);
+        current = parent;
+    }
+    nodes
+}
+
+/// The path ending at `parents[index]`, following parent indices back to the start.
+fn trace_parents<N: Copy>(parents: &[(N, usize)], mut index: usize) -> Path<N> {
+    let mut nodes = vec![parents[index].0];
+    while index != 0 {
+        index = parents[index].1;
+        nodes.push(parents[index].0);
+    }
+    nodes.reverse();
+    Path { nodes }
+}
+
+/// Extend `partial`, which runs backwards from a goal, through every parent
+/// back to the start, adding each finished path to `result`.
+fn collect_paths<N>(
+    parents: &HashMap<N, (usize, Vec<N>)>,
+    partial: &mut Vec<N>,
+    result: &mut Vec<Path<N>>,
+) where
+    N: Eq + Hash + Copy,
+{
+    let current = partial[partial.len() - 1];
+    let previous = &parents[&current].1;
+    if previous.is_empty() {
+        let mut nodes = partial.clone();
+        nodes.reverse();
+        result.push(Path { nodes });
+        return;
+    }
+    for &node in previous {
+        partial.push(node);
+        collect_paths(parents, partial, result);
+        partial.pop();
+    }
+}
+
 /// Find all nodes reachable from start using flood-fill.
 ///
 /// Returns a set of all connected nodes (including start).
@@ -60,13 +358,22 @@ where
     N: Eq + Hash + Copy,
     FN: Fn(&N) -> Vec<N>,
 {
-    let mut visited: HashSet<N> = HashSet::from([start]);
+    flood_fill_with(start, neighbors, HashSet::new())
+}
+
+/// Like [`flood_fill`], marking every reached node in `visited` and returning it.
+pub fn flood_fill_with<N, FN, V>(start: N, neighbors: FN, mut visited: V) -> V
+where
+    N: Copy,
+    FN: Fn(&N) -> Vec<N>,
+    V: Visited<N>,
+{
+    visited.visit(start);
     let mut queue: VecDeque<N> = VecDeque::from([start]);
 
     while let Some(current) = queue.pop_front() {
         for next in neighbors(&current) {
-            if !visited.contains(&next) {
-                visited.insert(next);
+            if visited.visit(next) {
                 queue.push_back(next);
             }
         }
@@ -88,17 +395,32 @@ where
     N: Eq + Hash + Copy,
     FN: Fn(&N) -> Vec<N>,
     FG: Fn(&N) -> bool,
+{
+    shortest_distance_with(start, neighbors, is_goal, HashSet::new())
+}
+
+/// Like [`shortest_distance`], tracking visited nodes in `visited`.
+pub fn shortest_distance_with<N, FN, FG>(
+    start: N,
+    neighbors: FN,
+    is_goal: FG,
+    mut visited: impl Visited<N>,
+) -> Option<usize>
+where
+    N: Copy,
+    FN: Fn(&N) -> Vec<N>,
+    FG: Fn(&N) -> bool,
 {
     if is_goal(&start) {
         return Some(0);
     }
 
-    let mut visited: HashSet<N> = HashSet::from([start]);
+    visited.visit(start);
     let mut queue: VecDeque<(N, usize)> = VecDeque::from([(start, 0)]);
 
     while let Some((current, dist)) = queue.pop_front() {
         for next in neighbors(&current) {
-            if visited.contains(&next) {
+            if !visited.visit(next) {
                 continue;
             }
 
@@ -106,7 +428,6 @@ where
                 return Some(dist + 1);
             }
 
-            visited.insert(next);
             queue.push_back((next, dist + 1));
         }
     }
@@ -114,6 +435,122 @@ where
     None
 }
 
+/// Find the distance to every node reachable from any of the sources.
+///
+/// Returns a map from each reached node to its number of steps from the
+/// nearest source; sources are at distance 0.
+///
+/// # Arguments
+/// * `sources` - The starting nodes
+/// * `neighbors` - Function returning neighboring nodes
+pub fn distances<N, FN>(sources: impl IntoIterator<Item = N>, neighbors: FN) -> HashMap<N, usize>
+where
+    N: Eq + Hash + Copy,
+    FN: Fn(&N) -> Vec<N>,
+{
+    let mut result = HashMap::new();
+    let mut queue = VecDeque::new();
+    for source in sources {
+        if result.insert(source, 0).is_none() {
+            queue.push_back(source);
+        }
+    }
+
+    while let Some(current) = queue.pop_front() {
+        let dist = result[&current];
+        for next in neighbors(&current) {
+            if let Entry::Vacant(entry) = result.entry(next) {
+                entry.insert(dist + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    result
+}
+
+/// Like [`distances`] over the cells of a `height` x `width` grid, returning a
+/// grid of distances with `None` for unreachable cells.
+pub fn grid_distances<FN>(
+    sources: impl IntoIterator<Item = Location>,
+    neighbors: FN,
+    height: usize,
+    width: usize,
+) -> Grid<Option<usize>>
+where
+    FN: Fn(&Location) -> Vec<Location>,
+{
+    let mut result = Grid::new(height, width, None);
+    let mut queue = VecDeque::new();
+    for source in sources {
+        if result[source].replace(0).is_none() {
+            queue.push_back(source);
+        }
+    }
+
+    while let Some(current) = queue.pop_front() {
+        let dist = result[current].expect("queued cells have a distance");
+        for next in neighbors(&current) {
+            if result[next].is_none() {
+                result[next] = Some(dist + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    result
+}
+
+/// Find the node each reachable node was first reached from.
+///
+/// Sources have no entry. Walking the map back from any node with
+/// [`path_to`] gives a shortest path to it from the nearest source.
+///
+/// # Arguments
+/// * `sources` - The starting nodes
+/// * `neighbors` - Function returning neighboring nodes
+pub fn predecessors<N, FN>(sources: impl IntoIterator<Item = N>, neighbors: FN) -> HashMap<N, N>
+where
+    N: Eq + Hash + Copy,
+    FN: Fn(&N) -> Vec<N>,
+{
+    let mut visited = HashSet::new();
+    let mut result = HashMap::new();
+    let mut queue: VecDeque<N> = sources
+        .into_iter()
+        .filter(|&source| visited.insert(source))
+        .collect();
+
+    while let Some(current) = queue.pop_front() {
+        for next in neighbors(&current) {
+            if visited.insert(next) {
+                result.insert(next, current);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    result
+}
+
+/// The path from a source to `target` recorded in a [`predecessors`] map,
+/// starting with the source and ending with `target`.
+///
+/// A `target` that is not in the map is treated as a source itself.
+pub fn path_to<N>(predecessors: &HashMap<N, N>, target: N) -> Path<N>
+where
+    N: Eq + Hash + Copy,
+{
+    let mut path = vec![target];
+    let mut current = target;
+    while let Some(&previous) = predecessors.get(&current) {
+        path.push(previous);
+        current = previous;
+    }
+    path.reverse();
+    Path { nodes: path }
+}
+
 /// Traverse all nodes reachable from start, calling a visitor function on each.
 ///
 /// The visitor receives each node as it's discovered.
@@ -124,21 +561,34 @@ where
 /// * `start` - The starting node
 /// * `neighbors` - Function returning valid neighboring nodes
 /// * `visitor` - Function called for each discovered node
-pub fn traverse<N, FN, FV>(start: N, neighbors: FN, mut visitor: FV)
+pub fn traverse<N, FN, FV>(start: N, neighbors: FN, visitor: FV)
 where
     N: Eq + Hash + Copy,
     FN: Fn(&N) -> Vec<N>,
     FV: FnMut(&N),
 {
-    let mut visited: HashSet<N> = HashSet::from([start]);
+    traverse_with(start, neighbors, visitor, HashSet::new());
+}
+
+/// Like [`traverse`], tracking visited nodes in `visited`.
+pub fn traverse_with<N, FN, FV>(
+    start: N,
+    neighbors: FN,
+    mut visitor: FV,
+    mut visited: impl Visited<N>,
+) where
+    N: Copy,
+    FN: Fn(&N) -> Vec<N>,
+    FV: FnMut(&N),
+{
+    visited.visit(start);
     let mut queue: VecDeque<N> = VecDeque::from([start]);
 
     visitor(&start);
 
     while let Some(current) = queue.pop_front() {
         for next in neighbors(&current) {
-            if !visited.contains(&next) {
-                visited.insert(next);
+            if visited.visit(next) {
                 visitor(&next);
                 queue.push_back(next);
             }
@@ -161,7 +611,22 @@ where
     FN: Fn(&N) -> Vec<N>,
     FG: Fn(&N) -> bool,
 {
-    let mut visited: HashSet<N> = HashSet::from([start]);
+    build_path_with(start, neighbors, is_end, HashSet::new())
+}
+
+/// Like [`build_path`], tracking visited nodes in `visited`.
+pub fn build_path_with<N, FN, FG>(
+    start: N,
+    neighbors: FN,
+    is_end: FG,
+    mut visited: impl Visited<N>,
+) -> Vec<N>
+where
+    N: Copy,
+    FN: Fn(&N) -> Vec<N>,
+    FG: Fn(&N) -> bool,
+{
+    visited.visit(start);
     let mut path: Vec<N> = vec![start];
     let mut queue: VecDeque<N> = VecDeque::from([start]);
 
@@ -171,8 +636,7 @@ where
         }
 
         for next in neighbors(&current) {
-            if !visited.contains(&next) {
-                visited.insert(next);
+            if visited.visit(next) {
                 path.push(next);
                 queue.push_back(next);
             }
@@ -181,3 +645,101 @@ where
 
     path
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_neighbors(n: &i32) -> Vec<i32> {
+        [n - 1, n + 1]
+            .into_iter()
+            .filter(|next| (0..10).contains(next))
+            .collect()
+    }
+
+    #[test]
+    fn test_distances() {
+        let dist = distances([2, 8], line_neighbors);
+        assert_eq!(dist.len(), 10);
+        assert_eq!((dist[&0], dist[&5], dist[&8], dist[&9]), (2, 3, 0, 1));
+
+        let previous = predecessors([2], line_neighbors);
+        assert_eq!(path_to(&previous, 5).nodes(), [2, 3, 4, 5]);
+        assert!(path_to(&previous, 2).is_empty());
+    }
+
+    #[test]
+    fn test_search() {
+        let path = search(2, line_neighbors, |&n| n == 5).unwrap();
+        assert_eq!(path.nodes(), [2, 3, 4, 5]);
+        assert_eq!((path.len(), *path.start(), *path.goal()), (3, 2, 5));
+        assert!(search(2, line_neighbors, |&n| n == 2).unwrap().is_empty());
+        assert_eq!(search(2, line_neighbors, |&n| n == 10), None);
+    }
+
+    #[test]
+    fn test_search_all_shortest() {
+        // a 3x3 grid of open cells has 6 shortest routes between opposite corners
+        let neighbors = |&(r, c): &(i32, i32)| {
+            [(r + 1, c), (r, c + 1), (r - 1, c), (r, c - 1)]
+                .into_iter()
+                .filter(|&(r, c)| (0..3).contains(&r) && (0..3).contains(&c))
+                .collect()
+        };
+        let paths = search_all_shortest((0, 0), neighbors, |&n| n == (2, 2));
+        assert_eq!(paths.len(), 6);
+        assert!(paths.iter().all(|path| path.len() == 4));
+        assert!(paths.iter().all(|path| path.start() == &(0, 0)));
+
+        let first = search((0, 0), neighbors, |&n| n == (2, 2)).unwrap();
+        assert!(paths.contains(&first));
+        assert!(search_all_shortest(0, line_neighbors, |&n| n == 10).is_empty());
+    }
+
+    #[test]
+    fn test_bidirectional() {
+        // one-way steps of +1 and *2, searched backwards with -1 and /2
+        let forward = |&n: &u32| vec![n + 1, n * 2];
+        let backward = |&n: &u32| {
+            let mut previous = vec![];
+            if n > 1 {
+                previous.push(n - 1);
+            }
+            if n % 2 == 0 {
+                previous.push(n / 2);
+            }
+            previous
+        };
+        let path = bidirectional(1, 100, forward, backward).unwrap();
+        let expected = search(1, forward, |&n| n == 100).unwrap();
+        assert_eq!(path.len(), expected.len());
+        assert_eq!((*path.start(), *path.goal()), (1, 100));
+        assert!(path
+            .nodes()
+            .windows(2)
+            .all(|w| forward(&w[0]).contains(&w[1])));
+
+        assert!(bidirectional(3, 3, forward, backward).unwrap().is_empty());
+        assert_eq!(
+            bidirectional(0, 9, line_neighbors, line_neighbors)
+                .unwrap()
+                .len(),
+            9
+        );
+        assert_eq!(bidirectional(0, 12, line_neighbors, line_neighbors), None);
+    }
+
+    #[test]
+    fn test_grid_distances() {
+        let grid: Grid<char> = "..#\n.##\n...".parse().unwrap();
+        let start = Location { row: 0, col: 0 };
+        let dist = grid_distances(
+            [start],
+            |&loc| grid.neighbors_where(loc, |c| c == '.'),
+            grid.height(),
+            grid.width(),
+        );
+        assert_eq!(dist[Location { row: 2, col: 2 }], Some(4));
+        assert_eq!(dist[Location { row: 0, col: 2 }], None);
+    }
+}
@@ -1,44 +1,22 @@
-advent_of_code::solution!(5);
-
-fn parse_input_rules(input: &str) -> Vec<(u64, u64)> {
-    let mut result: Vec<(u64, u64)> = Vec::new();
-
-    for line in input.lines() {
-        if line.trim().is_empty() {
-            break;
-        }
+use advent_of_code::input::{ParseError, Span};
 
-        let mut iter = line.split('|');
-        let before = iter.next().unwrap().parse::<u64>().unwrap();
-        let after = iter.next().unwrap().parse::<u64>().unwrap();
-        result.push((before, after));
-    }
+advent_of_code::solution!(5);
 
-    result
+fn parse_input_rules(input: &str) -> Result<Vec<(u64, u64)>, ParseError> {
+    let [rules, _] = Span::new(input).sections_exact()?;
+    rules.lines().map(|line| line.key_value("|")).collect()
 }
 
-fn parse_input_updates(input: &str) -> Vec<Vec<u64>> {
-    let mut result: Vec<Vec<u64>> = Vec::new();
-    let mut is_past_rules = false;
-
-    for line in input.lines() {
-        if line.trim().is_empty() {
-            is_past_rules = true;
-            continue;
-        } else if !is_past_rules {
-            continue;
-        }
-
-        let update: Vec<u64> = line.split(',').map(|d| d.parse::<u64>().unwrap()).collect();
-        result.push(update);
-    }
-
-    result
+fn parse_input_updates(input: &str) -> Result<Vec<Vec<u64>>, ParseError> {
+    let [_, updates] = Span::new(input).sections_exact()?;
+    updates.lines().map(|line| line.separated(",")).collect()
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let rules = advent_of_code::order_rules::Rules::from(parse_input_rules(input));
-    let updates = parse_input_updates(input);
+    let rules = advent_of_code::order_rules::Rules::from(
+        parse_input_rules(input).unwrap_or_else(|e| panic!("{e}")),
+    );
+    let updates = parse_input_updates(input).unwrap_or_else(|e| panic!("{e}"));
     let sum = updates
         .into_iter()
         .filter(|u| rules.is_valid(u))
@@ -49,8 +27,10 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let rules = advent_of_code::order_rules::Rules::from(parse_input_rules(input));
-    let mut updates = parse_input_updates(input);
+    let rules = advent_of_code::order_rules::Rules::from(
+        parse_input_rules(input).unwrap_or_else(|e| panic!("{e}")),
+    );
+    let mut updates = parse_input_updates(input).unwrap_or_else(|e| panic!("{e}"));
     updates.retain(|u| !rules.is_valid(u));
 
     let fixed: Vec<Vec<u64>> = updates.iter().map(|u| rules.make_valid(u)).collect();
@@ -65,4 +65,19 @@ mod tests {
             "examples", DAY, 3,
         ));
     }
+
+    #[test]
+    fn test_walk_observed() {
+        let map = advent_of_code::input::parse_2d_vector(&advent_of_code::template::read_file(
+            "examples", DAY,
+        ));
+        let pathfinder = advent_of_code::pathfinder::Pathfinder::from(&map);
+        let mut recorder =
+            advent_of_code::viz::Recorder::new(advent_of_code::viz::Palette::default(), 1);
+        pathfinder.walk_observed(&mut recorder);
+
+        let last = recorder.frames().last().unwrap();
+        let visited = last.cells().filter(|(_, &c)| c != '.' && c != '#').count();
+        assert_eq!(visited, 41);
+    }
 }
@@ -1,24 +1,19 @@
 advent_of_code::solution!(14);
 
-use regex::Regex;
+use advent_of_code::input::Span;
 
 fn parse_input(input: &str) -> Vec<advent_of_code::security::Robot> {
     let mut result = Vec::new();
-    let re = Regex::new(r"^p=(\d+),(\d+) v=(-?\d+),(-?\d+)").unwrap();
 
-    for line in input.lines() {
-        let (_, [col_pos, row_pos, col_v, row_v]) =
-            re.captures_iter(line).next().unwrap().extract();
+    for line in Span::new(input).lines().filter(|line| !line.is_blank()) {
+        let (col_pos, row_pos, col_v, row_v) =
+            advent_of_code::scan!(line, "p={},{} v={},{}", usize, usize, i32, i32)
+                .unwrap_or_else(|e| panic!("{e}"));
 
-        let col_pos = col_pos.parse::<usize>().unwrap();
-        let row_pos = row_pos.parse::<usize>().unwrap();
         let position = advent_of_code::grid::Location {
             row: row_pos,
             col: col_pos,
         };
-
-        let col_v = col_v.parse::<i32>().unwrap();
-        let row_v = row_v.parse::<i32>().unwrap();
         let velocity = advent_of_code::security::Velocity { col_v, row_v };
 
         result.push(advent_of_code::security::Robot::from((position, velocity)));
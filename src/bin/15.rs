@@ -1,4 +1,5 @@
 use advent_of_code::grid;
+use advent_of_code::lanternfish::Tile;
 
 advent_of_code::solution!(15);
 
@@ -8,36 +9,21 @@ fn parse_robot_steps(input: &str) -> Vec<grid::Direction> {
     steps.chars().map(grid::to_direction).collect()
 }
 
-fn parse_standard_input(input: &str) -> (Vec<Vec<char>>, Vec<grid::Direction>) {
-    let map_end = input.find("\n\n").unwrap();
-    let map = advent_of_code::input::parse_2d_vector(&input[0..map_end]);
+fn parse_standard_input(input: &str) -> (grid::Grid<Tile>, Vec<grid::Direction>) {
+    let (map, robot_steps): (_, String) = advent_of_code::input::sections2(input);
 
-    let robot_steps = parse_robot_steps(&input[map_end..input.len()]);
-
-    (map, robot_steps)
+    (map, parse_robot_steps(&robot_steps))
 }
 
-fn parse_wide_input(input: &str) -> (Vec<Vec<char>>, Vec<grid::Direction>) {
-    let mut map: Vec<Vec<char>> = Vec::new();
-
-    let map_end = input.find("\n\n").unwrap();
-    let map_slice = &input[0..map_end];
-    for line in map_slice.lines() {
-        let mut row = String::new();
-        for c in line.chars() {
-            row.push_str(match c {
-                '#' => "##",
-                'O' => "[]",
-                '.' => "..",
-                '@' => "@.",
-                e => panic!("Parsed unexpected map character {e}"),
-            });
-        }
-        let row: Vec<char> = row.chars().collect();
-        map.push(row);
-    }
-
-    let robot_steps = parse_robot_steps(&input[map_end..input.len()]);
+fn parse_wide_input(input: &str) -> (grid::Grid<Tile>, Vec<grid::Direction>) {
+    let (map, robot_steps) = parse_standard_input(input);
+    let map = map.expand_columns(|&tile| match tile {
+        Tile::Wall => [Tile::Wall, Tile::Wall],
+        Tile::Box => [Tile::BoxLeft, Tile::BoxRight],
+        Tile::Floor => [Tile::Floor, Tile::Floor],
+        Tile::Robot => [Tile::Robot, Tile::Floor],
+        tile => panic!("Parsed unexpected tile {tile}"),
+    });
 
     (map, robot_steps)
 }
@@ -46,7 +32,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     let (warehouse, robot_steps) = parse_standard_input(input);
     let mut lanternfish = advent_of_code::lanternfish::Lanternfish::from(warehouse);
 
-    robot_steps.iter().for_each(|&d| lanternfish.move_robot(d));
+    lanternfish.move_robots(&robot_steps);
     let result = lanternfish.gps_sum();
 
     Some(result)
@@ -56,7 +42,7 @@ pub fn part_two(input: &str) -> Option<u64> {
     let (warehouse, robot_steps) = parse_wide_input(input);
     let mut lanternfish = advent_of_code::lanternfish::Lanternfish::from(warehouse);
 
-    robot_steps.iter().for_each(|&d| lanternfish.move_robot(d));
+    lanternfish.move_robots(&robot_steps);
     let result = lanternfish.gps_sum();
 
     Some(result)
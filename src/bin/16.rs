@@ -1,7 +1,7 @@
 advent_of_code::solution!(16);
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let map = advent_of_code::input::parse_2d_vector(input);
+    let map = advent_of_code::input::parse_grid(input);
     let maze = advent_of_code::maze::Maze::from(map);
     let result = maze.shortest_path();
 
@@ -9,7 +9,7 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let map = advent_of_code::input::parse_2d_vector(input);
+    let map = advent_of_code::input::parse_grid(input);
     let maze = advent_of_code::maze::Maze::from(map);
     let result = maze.shortest_path_tiles();
 
@@ -1,33 +1,19 @@
-use regex::Regex;
+use advent_of_code::input::{ParseError, Span};
 
 advent_of_code::solution!(17);
 
-fn parse_input(input: &str) -> (advent_of_code::assembly::Computer, Vec<u8>) {
-    let register_re = Regex::new(r"Register [ABC]: (\d+)").unwrap();
-    let program_re = Regex::new(r"^Program: (\d(?:,\d)*)$").unwrap();
+fn parse_input(input: &str) -> Result<(advent_of_code::assembly::Computer, Vec<u8>), ParseError> {
+    let [registers, program] = Span::new(input).sections_exact()?;
+    let [a, b, c] = registers.ints::<u64>()?[..] else {
+        return Err(registers.error("expected registers A, B and C"));
+    };
+    let program: Vec<u8> = program.strip_prefix("Program: ")?.ints()?;
 
-    let mut lines = input.lines();
-    let a = register_re.captures(lines.next().unwrap()).unwrap()[1]
-        .parse::<u64>()
-        .unwrap();
-    let b = register_re.captures(lines.next().unwrap()).unwrap()[1]
-        .parse::<u64>()
-        .unwrap();
-    let c = register_re.captures(lines.next().unwrap()).unwrap()[1]
-        .parse::<u64>()
-        .unwrap();
-    lines.next();
-
-    let program: Vec<u8> = program_re.captures(lines.next().unwrap()).unwrap()[1]
-        .split(',')
-        .map(|s| s.parse::<u8>().unwrap())
-        .collect();
-
-    (advent_of_code::assembly::Computer::new(a, b, c), program)
+    Ok((advent_of_code::assembly::Computer::new(a, b, c), program))
 }
 
 pub fn part_one(input: &str) -> Option<String> {
-    let (mut computer, program) = parse_input(input);
+    let (mut computer, program) = parse_input(input).unwrap_or_else(|e| panic!("{e}"));
     let output = computer.execute(&program);
 
     let mut result = String::new();
@@ -39,7 +25,7 @@ pub fn part_one(input: &str) -> Option<String> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let (_, program) = parse_input(input);
+    let (_, program) = parse_input(input).unwrap_or_else(|e| panic!("{e}"));
     let result = advent_of_code::assembly::magic_register(program);
 
     Some(result)
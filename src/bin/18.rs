@@ -1,27 +1,26 @@
 use std::collections::VecDeque;
 
-use advent_of_code::{bytedodge, grid};
-use regex::Regex;
+use advent_of_code::bytedodge::{self, Memory};
+use advent_of_code::grid;
+use advent_of_code::input;
 
 advent_of_code::solution!(18);
 
 fn parse_input(input: &str) -> VecDeque<grid::Location> {
-    let re = Regex::new(r"(\d+),(\d+)").unwrap();
-    let mut result = VecDeque::new();
-
-    input.lines().for_each(|line| {
-        let (_, [col, row]) = re.captures(line).unwrap().extract();
-        let location = grid::Location {
-            row: row.parse::<usize>().unwrap(),
-            col: col.parse::<usize>().unwrap(),
-        };
-        result.push_back(location);
-    });
-
-    result
+    input::ints(input)
+        .chunks_exact(2)
+        .map(|pair| grid::Location {
+            row: pair[1],
+            col: pair[0],
+        })
+        .collect()
 }
 
-fn add_corruptions(grid: &mut [Vec<char>], corruptions: &VecDeque<grid::Location>, count: usize) {
+fn add_corruptions(
+    grid: &mut grid::Grid<Memory>,
+    corruptions: &VecDeque<grid::Location>,
+    count: usize,
+) {
     if count >= corruptions.len() {
         panic!(
             "Requested {count} corruptions, but only {} provided",
@@ -30,12 +29,12 @@ fn add_corruptions(grid: &mut [Vec<char>], corruptions: &VecDeque<grid::Location
     }
 
     for obstacle in corruptions.iter().take(count) {
-        grid[obstacle.row][obstacle.col] = '#';
+        grid[*obstacle] = Memory::Corrupted;
     }
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let mut grid = grid::create_grid(71, 71, '.');
+    let mut grid = grid::Grid::new(71, 71, Memory::Safe);
 
     let corruptions = parse_input(input);
     add_corruptions(&mut grid, &corruptions, 1024);
@@ -56,7 +55,7 @@ pub fn part_two(input: &str) -> Option<String> {
     while left < right {
         let mid = left + (right - left) / 2;
 
-        let mut grid = grid::create_grid(71, 71, '.');
+        let mut grid = grid::Grid::new(71, 71, Memory::Safe);
         add_corruptions(&mut grid, &corruptions, mid + 1);
 
         let bytedodge = bytedodge::ByteDodge::from(grid);
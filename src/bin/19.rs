@@ -3,18 +3,17 @@ use advent_of_code::towel::{Pattern, Towel};
 advent_of_code::solution!(19);
 
 fn parse_input(input: &str) -> (Vec<Pattern>, Vec<Towel>) {
-    let mut lines = input.lines();
+    let (patterns, towels): (String, String) = advent_of_code::input::sections2(input);
 
-    let patterns: Vec<Pattern> = lines
-        .next()
-        .unwrap()
+    let patterns: Vec<Pattern> = patterns
         .split(", ")
         .map(|s| Pattern::from(s.to_string()))
         .collect();
 
-    lines.next(); // skip empty line
-
-    let towels: Vec<Towel> = lines.map(|line| Towel::from(line.to_string())).collect();
+    let towels: Vec<Towel> = towels
+        .lines()
+        .map(|line| Towel::from(line.to_string()))
+        .collect();
 
     (patterns, towels)
 }
@@ -1,17 +1,17 @@
 use advent_of_code::cheat::Cheat;
-use advent_of_code::input::parse_2d_vector;
+use advent_of_code::input::parse_grid;
 
 advent_of_code::solution!(20);
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let grid = parse_2d_vector(input);
+    let grid = parse_grid(input);
     let cheat = Cheat::from(grid);
     let result = cheat.count_cheats(100, 2);
     Some(result)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let grid = parse_2d_vector(input);
+    let grid = parse_grid(input);
     let cheat = Cheat::from(grid);
     let result = cheat.count_cheats(100, 20);
     Some(result)
@@ -35,7 +35,7 @@ mod tests {
 
     #[test]
     fn test_cheats_threshold_2() {
-        let grid = parse_2d_vector(&advent_of_code::template::read_file("examples", DAY));
+        let grid = parse_grid(&advent_of_code::template::read_file("examples", DAY));
         let cheat = Cheat::from(grid);
         // 14 + 14 + 2 + 4 + 2 + 3 + 1 + 1 + 1 + 1 + 1 = 44 total cheats saving >= 2
         assert_eq!(cheat.count_cheats(2, 2), 44);
@@ -43,7 +43,7 @@ mod tests {
 
     #[test]
     fn test_cheats_threshold_4() {
-        let grid = parse_2d_vector(&advent_of_code::template::read_file("examples", DAY));
+        let grid = parse_grid(&advent_of_code::template::read_file("examples", DAY));
         let cheat = Cheat::from(grid);
         // 14 + 2 + 4 + 2 + 3 + 1 + 1 + 1 + 1 + 1 = 30 total cheats saving >= 4
         assert_eq!(cheat.count_cheats(4, 2), 30);
@@ -51,7 +51,7 @@ mod tests {
 
     #[test]
     fn test_cheats_threshold_8() {
-        let grid = parse_2d_vector(&advent_of_code::template::read_file("examples", DAY));
+        let grid = parse_grid(&advent_of_code::template::read_file("examples", DAY));
         let cheat = Cheat::from(grid);
         // 4 + 2 + 3 + 1 + 1 + 1 + 1 + 1 = 14 total cheats saving >= 8
         assert_eq!(cheat.count_cheats(8, 2), 14);
@@ -59,7 +59,7 @@ mod tests {
 
     #[test]
     fn test_cheats_threshold_10() {
-        let grid = parse_2d_vector(&advent_of_code::template::read_file("examples", DAY));
+        let grid = parse_grid(&advent_of_code::template::read_file("examples", DAY));
         let cheat = Cheat::from(grid);
         // 2 + 3 + 1 + 1 + 1 + 1 + 1 = 10 total cheats saving >= 10
         assert_eq!(cheat.count_cheats(10, 2), 10);
@@ -67,7 +67,7 @@ mod tests {
 
     #[test]
     fn test_cheats_threshold_64() {
-        let grid = parse_2d_vector(&advent_of_code::template::read_file("examples", DAY));
+        let grid = parse_grid(&advent_of_code::template::read_file("examples", DAY));
         let cheat = Cheat::from(grid);
         // 1 cheat saving >= 64
         assert_eq!(cheat.count_cheats(64, 2), 1);
@@ -1,40 +1,42 @@
+use advent_of_code::input::{ParseError, Span};
 use advent_of_code::logic::{Gate, Logic, Operation};
 use std::collections::HashMap;
 
 advent_of_code::solution!(24);
 
-fn parse_input(input: &str) -> Logic {
-    let sections: Vec<&str> = input.split("\n\n").collect();
+fn parse_gate(line: Span) -> Result<(String, String, String, Operation), ParseError> {
+    let (left, output) = line.split_once(" -> ")?;
+    let mut words = left.words();
+    let mut next_word = || words.next().ok_or_else(|| left.error("expected 3 words"));
+    let (input1, op, input2) = (next_word()?, next_word()?, next_word()?);
 
-    let mut wire_values: HashMap<String, Option<bool>> = HashMap::new();
-    for line in sections[0].lines() {
-        let parts: Vec<&str> = line.split(": ").collect();
-        let name = parts[0].to_string();
-        let value = parts[1] == "1";
-        wire_values.insert(name, Some(value));
-    }
+    let op = match op.text() {
+        "AND" => Operation::And,
+        "XOR" => Operation::Xor,
+        "OR" => Operation::Or,
+        _ => return Err(op.error("unknown operation")),
+    };
 
-    let mut gates = Vec::new();
-    for line in sections[1].lines() {
-        if line.is_empty() {
-            continue;
-        }
-
-        let parts: Vec<&str> = line.split(" -> ").collect();
-        let left = parts[0];
-        let output_name = parts[1].to_string();
+    Ok((input1.parse()?, input2.parse()?, output.parse()?, op))
+}
 
-        let left_parts: Vec<&str> = left.split_whitespace().collect();
-        let input1_name = left_parts[0].to_string();
-        let op_str = left_parts[1];
-        let input2_name = left_parts[2].to_string();
+fn parse_input(input: &str) -> Result<Logic, ParseError> {
+    let [wires, gate_lines] = Span::new(input).sections_exact()?;
 
-        let op = match op_str {
-            "AND" => Operation::And,
-            "XOR" => Operation::Xor,
-            "OR" => Operation::Or,
-            _ => panic!("Unknown operation: {}", op_str),
+    let mut wire_values: HashMap<String, Option<bool>> = HashMap::new();
+    for line in wires.lines() {
+        let (name, value) = line.split_once(": ")?;
+        let value = match value.text() {
+            "0" => false,
+            "1" => true,
+            _ => return Err(value.error("expected a wire value of 0 or 1")),
         };
+        wire_values.insert(name.parse()?, Some(value));
+    }
+
+    let mut gates = Vec::new();
+    for line in gate_lines.lines() {
+        let (input1_name, input2_name, output_name, op) = parse_gate(line)?;
 
         wire_values.entry(input1_name.clone()).or_insert(None);
         wire_values.entry(input2_name.clone()).or_insert(None);
@@ -44,11 +46,11 @@ fn parse_input(input: &str) -> Logic {
         gates.push(gate);
     }
 
-    Logic::new(gates, wire_values)
+    Ok(Logic::new(gates, wire_values))
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let mut logic = parse_input(input);
+    let mut logic = parse_input(input).unwrap_or_else(|e| panic!("{e}"));
     logic.propagate_until_stable();
     Some(logic.get_output_number())
 }
@@ -81,6 +83,12 @@ mod tests {
         assert_eq!(result, Some(56620966442854));
     }
 
+    #[test]
+    fn test_rejects_non_binary_wire() {
+        let error = parse_input("x00: 1\nx01: 7\n\nx00 AND x01 -> z00\n").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 6, "7"));
+    }
+
     #[test]
     #[ignore]
     fn test_part_two() {
@@ -92,7 +100,7 @@ mod tests {
     #[ignore]
     fn visualize_circuit() {
         let input = &advent_of_code::template::read_file("inputs", DAY);
-        let mut logic = parse_input(input);
+        let mut logic = parse_input(input).unwrap_or_else(|e| panic!("{e}"));
 
         // Propagate values before visualization to see the circuit state
         logic.propagate_until_stable();
@@ -121,7 +129,7 @@ mod tests {
     #[ignore]
     fn test_adder_detection() {
         let input = &advent_of_code::template::read_file("inputs", DAY);
-        let logic = parse_input(input);
+        let logic = parse_input(input).unwrap_or_else(|e| panic!("{e}"));
 
         // Find full adders first
         let (mut full_adders, full_adder_indices) = logic.find_full_adders();
@@ -1,50 +1,34 @@
 use advent_of_code::{
+    grid::{Grid, Location},
     input,
     lock::{Key, Lock},
 };
 
 advent_of_code::solution!(25);
 
-fn to_key(grid: Vec<Vec<char>>) -> Key {
-    let heights: Vec<usize> = (0..grid[0].len())
-        .map(|col| {
-            let mut height = 1;
-            loop {
-                if grid[6 - height][col] == '.' {
-                    break;
-                }
-                height += 1;
-            }
-            height - 1
-        })
-        .collect();
-    Key::from(heights)
+/// Count the `#` cells in each column below the solid top row.
+fn pin_heights(grid: &Grid<char>) -> Vec<usize> {
+    grid.columns()
+        .map(|col| col.skip(1).take_while(|&&c| c == '#').count())
+        .collect()
 }
 
-fn to_lock(grid: Vec<Vec<char>>) -> Lock {
-    let heights: Vec<usize> = (0..grid[0].len())
-        .map(|col| {
-            let mut height = 1;
-            loop {
-                if grid[height][col] == '.' {
-                    break;
-                }
-                height += 1;
-            }
-            height - 1
-        })
-        .collect();
-    Lock::from(heights)
+fn to_key(grid: Grid<char>) -> Key {
+    // Keys are solid along the bottom, so read them upside down like a lock.
+    Key::from(pin_heights(&grid.flip_vertical()))
+}
+
+fn to_lock(grid: Grid<char>) -> Lock {
+    Lock::from(pin_heights(&grid))
 }
 
 fn parse_input(input: &str) -> (Vec<Lock>, Vec<Key>) {
     let mut locks = Vec::new();
     let mut keys = Vec::new();
 
-    let sections: Vec<&str> = input.split("\n\n").collect();
-    for section in sections {
-        let grid = input::parse_2d_vector(section);
-        if grid[0][0] == '#' {
+    for section in input::sections(input) {
+        let grid = input::parse_grid(section);
+        if grid[Location { row: 0, col: 0 }] == '#' {
             locks.push(to_lock(grid));
         } else {
             keys.push(to_key(grid));
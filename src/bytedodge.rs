@@ -2,17 +2,25 @@
 //!
 //! Finds shortest path through a grid avoiding obstacles (#).
 
-use crate::grid::{self, Location, DIRECTIONS};
+use crate::grid::{Grid, Location};
 use crate::pathfinding;
 
+crate::cell_enum! {
+    /// A memory cell, which may have been corrupted by a falling byte.
+    pub enum Memory {
+        Safe = '.',
+        Corrupted = '#',
+    }
+}
+
 /// A grid with obstacles to navigate around.
 #[derive(Debug)]
 pub struct ByteDodge {
-    grid: Vec<Vec<char>>,
+    grid: Grid<Memory>,
 }
 
-impl From<Vec<Vec<char>>> for ByteDodge {
-    fn from(value: Vec<Vec<char>>) -> Self {
+impl From<Grid<Memory>> for ByteDodge {
+    fn from(value: Grid<Memory>) -> Self {
         Self { grid: value }
     }
 }
@@ -24,31 +32,25 @@ impl ByteDodge {
     pub fn min_steps(&self) -> u64 {
         let start = Location { row: 0, col: 0 };
         let end = Location {
-            row: self.grid.len() - 1,
-            col: self.grid[0].len() - 1,
+            row: self.grid.height() - 1,
+            col: self.grid.width() - 1,
         };
 
-        pathfinding::dijkstra(
+        pathfinding::astar(
             start,
             |loc| self.get_neighbors(loc),
+            pathfinding::manhattan(end),
             |loc| *loc == end,
         )
-        .unwrap_or(0)
+        .map_or(0, |(cost, _)| cost)
     }
 
     /// Get all passable neighbors of a location with uniform cost 1.
     fn get_neighbors(&self, loc: &Location) -> Vec<(Location, u64)> {
-        DIRECTIONS
-            .iter()
-            .filter_map(|&d| {
-                grid::get_location(&self.grid, *loc, d).and_then(|next| {
-                    if grid::at(&self.grid, next) != '#' {
-                        Some((next, 1))
-                    } else {
-                        None
-                    }
-                })
-            })
+        self.grid
+            .neighbors_where(*loc, |c| c == Memory::Safe)
+            .into_iter()
+            .map(|next| (next, 1))
             .collect()
     }
 }
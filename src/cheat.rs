@@ -3,88 +3,118 @@
 //! Analyzes paths through a maze to find "cheats" (shortcuts through walls).
 
 use crate::bfs;
-use crate::grid::{at, find_only, get_location, print_map, Location, DIRECTIONS};
+use crate::grid::{Grid, Location, Point, Renderer};
+use crate::template::{ANSI_BOLD, ANSI_DIM, ANSI_RED, ANSI_YELLOW};
 
 /// Cheat analyzer for a maze with a single path.
 #[derive(Debug)]
 pub struct Cheat {
-    grid: Vec<Vec<char>>,
-    path_order: Vec<Location>,
+    grid: Grid<char>,
+    end: Location,
+    from_start: Grid<Option<usize>>,
+    to_end: Grid<Option<usize>>,
 }
 
-impl From<Vec<Vec<char>>> for Cheat {
-    fn from(grid: Vec<Vec<char>>) -> Self {
-        let start = find_only(&grid, 'S');
-        let end = find_only(&grid, 'E');
+impl From<Grid<char>> for Cheat {
+    fn from(grid: Grid<char>) -> Self {
+        let start = grid.find_only('S');
+        let end = grid.find_only('E');
 
-        let path_order = bfs::build_path(
-            start,
-            |loc| {
-                DIRECTIONS
-                    .iter()
-                    .filter_map(|&d| {
-                        get_location(&grid, *loc, d).filter(|&next| {
-                            let c = at(&grid, next);
-                            c == '.' || c == 'S' || c == 'E'
-                        })
-                    })
-                    .collect()
-            },
-            |loc| *loc == end,
-        );
+        let track =
+            |loc: &Location| grid.neighbors_where(*loc, |c| c == '.' || c == 'S' || c == 'E');
+        let from_start = bfs::grid_distances([start], track, grid.height(), grid.width());
+        let to_end = bfs::grid_distances([end], track, grid.height(), grid.width());
 
-        Self { grid, path_order }
+        Self {
+            grid,
+            end,
+            from_start,
+            to_end,
+        }
     }
 }
 
 impl Cheat {
     pub fn print(&self) {
-        print_map(&self.grid);
+        self.grid.print();
     }
 
     pub fn print_path(&self) {
-        println!(
-            "Path order ({}): {:?}",
-            self.path_order.len(),
-            self.path_order
-        );
+        let path_order = self.path_order();
+        println!("Path order ({}):", path_order.len());
+        self.renderer()
+            .path(&path_order, ANSI_YELLOW)
+            .print(&self.grid);
+    }
+
+    /// Show the route taken when cheating from one path index to a later one.
+    pub fn print_cheat(&self, start: usize, end: usize) {
+        let path_order = self.path_order();
+        let (from, to) = (path_order[start], path_order[end]);
+        let skipped = Point::from(from).manhattan(Point::from(to)) as i64;
+        let saved = (end - start) as i64 - skipped;
+        println!("Cheat {from:?} -> {to:?} saves {saved} steps");
+        self.renderer()
+            .path(&path_order[..=start], ANSI_YELLOW)
+            .path(&path_order[end..], ANSI_YELLOW)
+            .highlight([from, to], &format!("{ANSI_BOLD}{ANSI_RED}"))
+            .print(&self.grid);
+    }
+
+    /// Every track cell in the order the path visits them, so a cell's
+    /// index is its distance from the start.
+    fn path_order(&self) -> Vec<Location> {
+        let mut path_order: Vec<Location> = self
+            .grid
+            .locations()
+            .filter(|&loc| self.from_start[loc].is_some())
+            .collect();
+        path_order.sort_by_key(|&loc| self.from_start[loc]);
+        path_order
+    }
+
+    fn renderer(&self) -> Renderer {
+        Renderer::new().color('#', ANSI_DIM)
     }
 
     /// Count cheats that save at least `threshold` steps.
     ///
     /// A cheat allows passing through walls for up to `max_distance` steps.
     pub fn count_cheats(&self, threshold: u64, max_distance: usize) -> u64 {
-        let mut count = 0;
-        let path_len = self.path_order.len();
-        let threshold_usize = threshold as usize;
+        let Some(track_len) = self.from_start[self.end] else {
+            return 0;
+        };
+        let threshold = threshold as usize;
+        let reach = max_distance as i64;
+        let (height, width) = self.grid.dimensions();
 
-        for start in 0..path_len {
-            let first_end = start + threshold_usize + 2;
-            if first_end >= path_len {
-                break;
-            }
-
-            for end in first_end..path_len {
-                let max_dist = std::cmp::min(end - (start + threshold_usize), max_distance);
-                if self.check_cheat(start, end, max_dist) {
-                    count += 1;
+        let mut count = 0;
+        for from in self.grid.locations() {
+            let Some(from_index) = self.from_start[from] else {
+                continue;
+            };
+            for dy in -reach..=reach {
+                let span = reach - dy.abs();
+                for dx in -span..=span {
+                    let Some(to) =
+                        (Point::from(from) + Point::new(dx, dy)).to_location(height, width)
+                    else {
+                        continue;
+                    };
+                    let Some(remaining) = self.to_end[to] else {
+                        continue;
+                    };
+                    let to_index = track_len - remaining;
+                    let skipped = (dx.abs() + dy.abs()) as usize;
+                    if to_index >= from_index + threshold + 2
+                        && to_index - from_index >= skipped + threshold
+                    {
+                        count += 1;
+                    }
                 }
             }
         }
 
         count
     }
-
-    /// Check if a cheat between two path indices is valid.
-    fn check_cheat(&self, index1: usize, index2: usize, max_distance: usize) -> bool {
-        let loc1 = self.path_order[index1];
-        let loc2 = self.path_order[index2];
-
-        let row_diff = loc1.row.abs_diff(loc2.row);
-        let col_diff = loc1.col.abs_diff(loc2.col);
-        let manhattan_distance = row_diff + col_diff;
-        let index_diff = index2.abs_diff(index1);
-
-        manhattan_distance < index_diff && manhattan_distance <= max_distance
-    }
 }
@@ -1,4 +1,4 @@
-use regex::Regex;
+use crate::input::{ParseError, Span};
 
 #[derive(Debug)]
 pub struct Claw {
@@ -81,14 +81,6 @@ impl Machine {
     }
 }
 
-#[derive(Debug)]
-enum InputType {
-    AButton,
-    BButton,
-    Prize,
-    Newline,
-}
-
 impl ClawBuilder {
     pub fn prize_offset(mut self, prize_offset: u64) -> ClawBuilder {
         self.prize_offset = prize_offset;
@@ -96,42 +88,11 @@ impl ClawBuilder {
     }
 
     pub fn machines(mut self, input: &str) -> ClawBuilder {
-        let button_regex =
-            Regex::new(r"^Button [AB]: X\+(\d+), Y\+(\d+)").expect("button regex should compile");
-        let prize_regex =
-            Regex::new(r"^Prize: X=(\d+), Y=(\d+)").expect("prize regex should compile");
-
-        let mut machines = Vec::new();
-        let mut input_type = InputType::AButton;
-        let mut a_button = Button {
-            cost: 0,
-            x: 0,
-            y: 0,
-        };
-        let mut b_button = a_button;
-
-        for line in input.lines() {
-            match input_type {
-                InputType::AButton => {
-                    a_button = ClawBuilder::create_button(line, &button_regex, ClawBuilder::A_COST)
-                }
-                InputType::BButton => {
-                    b_button = ClawBuilder::create_button(line, &button_regex, ClawBuilder::B_COST)
-                }
-                InputType::Prize => {
-                    let prize = ClawBuilder::create_prize(line, &prize_regex, self.prize_offset);
-                    let machine = Machine {
-                        a: a_button,
-                        b: b_button,
-                        prize,
-                    };
-                    machines.push(machine);
-                }
-                InputType::Newline => {}
-            }
-            input_type = ClawBuilder::next(input_type);
+        for section in Span::new(input).sections() {
+            let machine = ClawBuilder::parse_machine(section, self.prize_offset)
+                .unwrap_or_else(|e| panic!("{e}"));
+            self.machines.push(machine);
         }
-        self.machines.extend(machines);
 
         self
     }
@@ -145,35 +106,32 @@ impl ClawBuilder {
     const A_COST: u64 = 3;
     const B_COST: u64 = 1;
 
-    fn next(input_type: InputType) -> InputType {
-        match input_type {
-            InputType::AButton => InputType::BButton,
-            InputType::BButton => InputType::Prize,
-            InputType::Prize => InputType::Newline,
-            InputType::Newline => InputType::AButton,
-        }
-    }
-
-    fn create_button(line: &str, re: &Regex, cost: u64) -> Button {
-        let (_, [x, y]) = re
-            .captures_iter(line)
-            .next()
-            .expect("button line should match regex")
-            .extract();
-        let x = x.parse::<u64>().expect("button x should be valid u64");
-        let y = y.parse::<u64>().expect("button y should be valid u64");
-        Button { cost, x, y }
-    }
+    fn parse_machine(section: Span, prize_offset: u64) -> Result<Machine, ParseError> {
+        let mut lines = section.lines();
+        let mut next_line = || {
+            lines
+                .next()
+                .ok_or_else(|| section.error("expected two buttons and a prize"))
+        };
 
-    fn create_prize(line: &str, re: &Regex, prize_offset: u64) -> Prize {
-        let (_, [x, y]) = re
-            .captures_iter(line)
-            .next()
-            .expect("prize line should match regex")
-            .extract();
-        let x = x.parse::<u64>().expect("prize x should be valid u64") + prize_offset;
-        let y = y.parse::<u64>().expect("prize y should be valid u64") + prize_offset;
+        let (x, y) = crate::scan!(next_line()?, "Button A: X+{}, Y+{}", u64, u64)?;
+        let a = Button {
+            cost: ClawBuilder::A_COST,
+            x,
+            y,
+        };
+        let (x, y) = crate::scan!(next_line()?, "Button B: X+{}, Y+{}", u64, u64)?;
+        let b = Button {
+            cost: ClawBuilder::B_COST,
+            x,
+            y,
+        };
+        let (x, y) = crate::scan!(next_line()?, "Prize: X={}, Y={}", u64, u64)?;
+        let prize = Prize {
+            x: x + prize_offset,
+            y: y + prize_offset,
+        };
 
-        Prize { x, y }
+        Ok(Machine { a, b, prize })
     }
 }
@@ -0,0 +1,329 @@
+//! Depth-first search algorithms.
+//!
+//! The searches keep only the current path in memory, so they suit large
+//! implicit graphs where the breadth-first searches in [`crate::bfs`] would
+//! have to hold too many nodes at once. Path counting and ordering work on
+//! directed graphs given the same way, as a neighbors closure.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::bfs::Path;
+
+/// Search depth-first for a goal within `max_depth` steps of the start.
+///
+/// Returns the first path found, which need not be the shortest. Nodes already
+/// on the current path are skipped, so cycles cannot loop forever, but other
+/// nodes may be explored many times.
+///
+/// # Arguments
+/// * `start` - The starting node
+/// * `neighbors` - Function returning neighboring nodes for a given node
+/// * `is_goal` - Predicate to check if a node is the goal
+/// * `max_depth` - The most steps a path may take
+pub fn depth_limited<N, FN, FG>(
+    start: N,
+    neighbors: FN,
+    is_goal: FG,
+    max_depth: usize,
+) -> Option<Path<N>>
+where
+    N: Eq + Copy,
+    FN: Fn(&N) -> Vec<N>,
+    FG: Fn(&N) -> bool,
+{
+    let mut path = vec![start];
+    if descend(&mut path, &neighbors, &is_goal, max_depth) {
+        Some(Path::new(path))
+    } else {
+        None
+    }
+}
+
+/// Run [`depth_limited`] with limits of 0, 1, 2, ... up to `max_depth`.
+///
+/// Like [`crate::bfs::search`] this finds a shortest path, but it only keeps
+/// the current path in memory, at the cost of revisiting shallow nodes on
+/// every round.
+///
+/// # Arguments
+/// * `start` - The starting node
+/// * `neighbors` - Function returning neighboring nodes for a given node
+/// * `is_goal` - Predicate to check if a node is the goal
+/// * `max_depth` - The most steps a path may take
+pub fn iterative_deepening<N, FN, FG>(
+    start: N,
+    neighbors: FN,
+    is_goal: FG,
+    max_depth: usize,
+) -> Option<Path<N>>
+where
+    N: Eq + Copy,
+    FN: Fn(&N) -> Vec<N>,
+    FG: Fn(&N) -> bool,
+{
+    (0..=max_depth).find_map(|depth| depth_limited(start, &neighbors, &is_goal, depth))
+}
+
+/// Count the distinct paths from start to any goal.
+///
+/// Paths stop at the first goal they reach. The count from each node is
+/// remembered, so shared sub-paths are only walked once.
+///
+/// # Panics
+/// If a cycle is reachable from start, as it would allow infinitely many paths.
+///
+/// # Arguments
+/// * `start` - The starting node
+/// * `neighbors` - Function returning neighboring nodes for a given node
+/// * `is_goal` - Predicate to check if a node is a goal
+pub fn count_paths<N, FN, FG>(start: N, neighbors: FN, is_goal: FG) -> u64
+where
+    N: Eq + Hash + Copy,
+    FN: Fn(&N) -> Vec<N>,
+    FG: Fn(&N) -> bool,
+{
+    // `None` marks nodes whose count is still being worked out.
+    let mut counts: HashMap<N, Option<u64>> = HashMap::new();
+    count_from(start, &neighbors, &is_goal, &mut counts)
+}
+
+/// Every path from start to any goal that does not repeat a node.
+///
+/// Paths stop at the first goal they reach. The number of paths can grow
+/// exponentially with the size of the graph; use [`count_paths`] when only
+/// the number is needed.
+///
+/// # Arguments
+/// * `start` - The starting node
+/// * `neighbors` - Function returning neighboring nodes for a given node
+/// * `is_goal` - Predicate to check if a node is a goal
+pub fn all_paths<N, FN, FG>(start: N, neighbors: FN, is_goal: FG) -> Vec<Path<N>>
+where
+    N: Eq + Copy,
+    FN: Fn(&N) -> Vec<N>,
+    FG: Fn(&N) -> bool,
+{
+    let mut result = Vec::new();
+    let mut path = vec![start];
+    collect_paths(&mut path, &neighbors, &is_goal, &mut result);
+    result
+}
+
+/// Order the nodes reachable from `roots` so every node comes before the
+/// nodes it leads to.
+///
+/// Returns `None` if they contain a cycle; [`find_cycle`] will say where.
+///
+/// # Arguments
+/// * `roots` - The nodes to start from
+/// * `neighbors` - Function returning the nodes a node leads to
+pub fn topological_order<N, FN>(roots: impl IntoIterator<Item = N>, neighbors: FN) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Copy,
+    FN: Fn(&N) -> Vec<N>,
+{
+    let mut order = post_order(roots, neighbors).ok()?;
+    order.reverse();
+    Some(order)
+}
+
+/// A cycle among the nodes reachable from `roots`, if there is one.
+///
+/// The cycle is listed in order from where it was entered, without repeating
+/// that first node at the end.
+///
+/// # Arguments
+/// * `roots` - The nodes to start from
+/// * `neighbors` - Function returning the nodes a node leads to
+pub fn find_cycle<N, FN>(roots: impl IntoIterator<Item = N>, neighbors: FN) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Copy,
+    FN: Fn(&N) -> Vec<N>,
+{
+    post_order(roots, neighbors).err()
+}
+
+/// Number of paths from `node` to a goal, filling in `counts` along the way.
+fn count_from<N, FN, FG>(
+    node: N,
+    neighbors: &FN,
+    is_goal: &FG,
+    counts: &mut HashMap<N, Option<u64>>,
+) -> u64
+where
+    N: Eq + Hash + Copy,
+    FN: Fn(&N) -> Vec<N>,
+    FG: Fn(&N) -> bool,
+{
+    if is_goal(&node) {
+        return 1;
+    }
+    match counts.get(&node) {
+        Some(Some(count)) => return *count,
+        Some(None) => panic!("count_paths found a cycle, so there are infinitely many paths"),
+        None => {}
+    }
+
+    counts.insert(node, None);
+    let count = neighbors(&node)
+        .into_iter()
+        .map(|next| count_from(next, neighbors, is_goal, counts))
+        .sum();
+    counts.insert(node, Some(count));
+    count
+}
+
+/// Add every goal-reaching extension of `path` that does not repeat a node to `result`.
+fn collect_paths<N, FN, FG>(
+    path: &mut Vec<N>,
+    neighbors: &FN,
+    is_goal: &FG,
+    result: &mut Vec<Path<N>>,
+) where
+    N: Eq + Copy,
+    FN: Fn(&N) -> Vec<N>,
+    FG: Fn(&N) -> bool,
+{
+    let current = path[path.len() - 1];
+    if is_goal(&current) {
+        result.push(Path::new(path.clone()));
+        return;
+    }
+
+    for next in neighbors(&current) {
+        if path.contains(&next) {
+            continue;
+        }
+        path.push(next);
+        collect_paths(path, neighbors, is_goal, result);
+        path.pop();
+    }
+}
+
+/// Nodes reachable from `roots`, each after all the nodes it leads to, or the
+/// first cycle found.
+fn post_order<N, FN>(roots: impl IntoIterator<Item = N>, neighbors: FN) -> Result<Vec<N>, Vec<N>>
+where
+    N: Eq + Hash + Copy,
+    FN: Fn(&N) -> Vec<N>,
+{
+    // `false` while a node is on the current path, `true` once it is finished.
+    let mut finished: HashMap<N, bool> = HashMap::new();
+    let mut order = Vec::new();
+
+    for root in roots {
+        if finished.contains_key(&root) {
+            continue;
+        }
+        finished.insert(root, false);
+        // The current path, with the neighbors of each node still to visit.
+        let mut stack = vec![(root, neighbors(&root).into_iter())];
+
+        while let Some((node, pending)) = stack.last_mut() {
+            let node = *node;
+            match pending.next() {
+                Some(next) => match finished.get(&next) {
+                    Some(true) => {}
+                    Some(false) => {
+                        let entered = stack.iter().position(|(n, _)| *n == next).unwrap_or(0);
+                        return Err(stack[entered..].iter().map(|(n, _)| *n).collect());
+                    }
+                    None => {
+                        finished.insert(next, false);
+                        stack.push((next, neighbors(&next).into_iter()));
+                    }
+                },
+                None => {
+                    finished.insert(node, true);
+                    order.push(node);
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    Ok(order)
+}
+
+/// Extend `path` by up to `depth` steps until it ends at a goal, returning
+/// whether it does.
+fn descend<N, FN, FG>(path: &mut Vec<N>, neighbors: &FN, is_goal: &FG, depth: usize) -> bool
+where
+    N: Eq + Copy,
+    FN: Fn(&N) -> Vec<N>,
+    FG: Fn(&N) -> bool,
+{
+    let current = path[path.len() - 1];
+    if is_goal(&current) {
+        return true;
+    }
+    if depth == 0 {
+        return false;
+    }
+
+    for next in neighbors(&current) {
+        if path.contains(&next) {
+            continue;
+        }
+        path.push(next);
+        if descend(path, neighbors, is_goal, depth - 1) {
+            return true;
+        }
+        path.pop();
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iterative_deepening() {
+        let neighbors = |&n: &u32| vec![n + 1, n * 2];
+        let path = iterative_deepening(1, neighbors, |&n| n == 20, 10).unwrap();
+        assert_eq!(path.nodes(), [1, 2, 4, 5, 10, 20]);
+        assert_eq!(iterative_deepening(1, neighbors, |&n| n == 20, 4), None);
+
+        let deep = depth_limited(1, neighbors, |&n| n == 20, 10).unwrap();
+        assert!(deep.len() <= 10);
+        assert_eq!(*deep.goal(), 20);
+    }
+
+    #[test]
+    fn test_count_paths() {
+        // from n, step to n + 1 or n + 2; reaching 10 from 0 follows Fibonacci
+        let neighbors = |&n: &u32| (n + 1..=n + 2).filter(|&m| m <= 10).collect();
+        assert_eq!(count_paths(0, neighbors, |&n| n == 10), 89);
+        assert_eq!(all_paths(6, neighbors, |&n| n == 10).len(), 5);
+        assert_eq!(count_paths(0, neighbors, |&n| n == 11), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "cycle")]
+    fn test_count_paths_cycle() {
+        count_paths(0, |&n: &u32| vec![(n + 1) % 3], |&n| n == 5);
+    }
+
+    #[test]
+    fn test_topological_order() {
+        let edges = |&n: &char| match n {
+            'a' => vec!['b', 'c'],
+            'b' => vec!['d'],
+            'c' => vec!['d'],
+            _ => vec![],
+        };
+        let order = topological_order(['a'], edges).unwrap();
+        assert_eq!(order.len(), 4);
+        let position = |c| order.iter().position(|&n| n == c).unwrap();
+        assert!(position('a') < position('b') && position('b') < position('d'));
+        assert!(position('c') < position('d'));
+        assert_eq!(find_cycle(['a'], edges), None);
+
+        let cyclic = |&n: &u32| if n < 5 { vec![n + 1] } else { vec![2] };
+        assert_eq!(topological_order([0], cyclic), None);
+        assert_eq!(find_cycle([0], cyclic), Some(vec![2, 3, 4, 5]));
+    }
+}
@@ -0,0 +1,215 @@
+//! Compact visited-cell tracking for grids.
+//!
+//! Stores a handful of flags per cell in a flat byte array, so marking and
+//! checking a cell is one index computation and cloning is a single copy.
+
+use super::{Direction, Location};
+
+/// Flag for a cell visited without a direction.
+const VISITED: u8 = 1 << 4;
+
+/// A set of grid cells, where each cell can also record which directions it
+/// was entered in.
+///
+/// Plain [`insert`](BitGrid::insert) and per-direction
+/// [`insert_direction`](BitGrid::insert_direction) can be mixed; a cell is set
+/// once any of its flags is.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    flags: Vec<u8>,
+    height: usize,
+    width: usize,
+}
+
+impl BitGrid {
+    /// An empty set covering `height` x `width` cells.
+    pub fn new(height: usize, width: usize) -> Self {
+        Self {
+            flags: vec![0; height * width],
+            height,
+            width,
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// (height, width)
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    /// Set the cell, returning whether it was unset before.
+    pub fn insert(&mut self, location: Location) -> bool {
+        let index = self.index(location);
+        let was_set = self.flags[index] != 0;
+        self.flags[index] |= VISITED;
+        !was_set
+    }
+
+    /// Record that the cell was entered moving in `direction`, returning
+    /// whether that direction was new for the cell.
+    #[inline]
+    pub fn insert_direction(&mut self, location: Location, direction: Direction) -> bool {
+        let index = self.index(location);
+        let bit = direction_bit(direction);
+        let is_new = self.flags[index] & bit == 0;
+        self.flags[index] |= bit;
+        is_new
+    }
+
+    /// Clear every flag of the cell.
+    pub fn remove(&mut self, location: Location) {
+        let index = self.index(location);
+        self.flags[index] = 0;
+    }
+
+    /// Whether any flag of the cell is set.
+    #[inline]
+    pub fn is_set(&self, location: Location) -> bool {
+        self.flags[self.index(location)] != 0
+    }
+
+    #[inline]
+    pub fn has_direction(&self, location: Location, direction: Direction) -> bool {
+        self.flags[self.index(location)] & direction_bit(direction) != 0
+    }
+
+    /// Number of cells with any flag set.
+    pub fn count_ones(&self) -> usize {
+        self.flags.iter().filter(|&&f| f != 0).count()
+    }
+
+    /// The set cells in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = Location> + '_ {
+        self.flags
+            .iter()
+            .enumerate()
+            .filter(|(_, &f)| f != 0)
+            .map(|(index, _)| Location {
+                row: index / self.width,
+                col: index % self.width,
+            })
+    }
+
+    pub fn clear(&mut self) {
+        self.flags.fill(0);
+    }
+
+    /// Add every flag set in `other`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two grids have different dimensions.
+    pub fn union_with(&mut self, other: &BitGrid) {
+        self.combine(other, |a, b| a | b);
+    }
+
+    /// Keep only the cells that are also set in `other`, along with the flags
+    /// of both, so a plain [`insert`](BitGrid::insert) meets a per-direction
+    /// one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two grids have different dimensions.
+    pub fn intersect_with(&mut self, other: &BitGrid) {
+        self.combine(other, |a, b| if a != 0 && b != 0 { a | b } else { 0 });
+    }
+
+    pub fn union(&self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.union_with(other);
+        result
+    }
+
+    pub fn intersection(&self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.intersect_with(other);
+        result
+    }
+
+    fn combine(&mut self, other: &BitGrid, f: impl Fn(u8, u8) -> u8) {
+        assert_eq!(
+            self.dimensions(),
+            other.dimensions(),
+            "bit grids should have the same dimensions"
+        );
+        self.flags
+            .iter_mut()
+            .zip(&other.flags)
+            .for_each(|(a, &b)| *a = f(*a, b));
+    }
+
+    #[inline]
+    fn index(&self, location: Location) -> usize {
+        assert!(
+            location.row < self.height && location.col < self.width,
+            "location {location:?} is outside the {}x{} bit grid",
+            self.height,
+            self.width
+        );
+        location.row * self.width + location.col
+    }
+}
+
+#[inline]
+fn direction_bit(direction: Direction) -> u8 {
+    match direction {
+        Direction::Up => 1,
+        Direction::Down => 1 << 1,
+        Direction::Left => 1 << 2,
+        Direction::Right => 1 << 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_grid() {
+        let a = Location { row: 0, col: 1 };
+        let b = Location { row: 2, col: 0 };
+
+        let mut first = BitGrid::new(3, 2);
+        assert!(first.insert(a));
+        assert!(!first.insert(a));
+        assert!(first.insert_direction(b, Direction::Up));
+        assert!(!first.insert_direction(b, Direction::Up));
+        assert!(first.has_direction(b, Direction::Up));
+        assert!(!first.has_direction(b, Direction::Left));
+        assert!(!first.insert(b));
+        assert_eq!(first.count_ones(), 2);
+        assert_eq!(first.iter().collect::<Vec<_>>(), [a, b]);
+
+        let mut second = BitGrid::new(3, 2);
+        second.insert_direction(b, Direction::Up);
+        assert_eq!(first.intersection(&second).iter().collect::<Vec<_>>(), [b]);
+        second.insert(Location { row: 1, col: 1 });
+        assert_eq!(first.union(&second).count_ones(), 3);
+
+        first.remove(a);
+        assert!(!first.is_set(a));
+    }
+
+    #[test]
+    fn test_intersect_mixed_flags() {
+        let a = Location { row: 0, col: 0 };
+        let b = Location { row: 1, col: 1 };
+
+        let mut plain = BitGrid::new(2, 2);
+        plain.insert(a);
+        plain.insert(b);
+        let mut directed = BitGrid::new(2, 2);
+        directed.insert_direction(a, Direction::Left);
+
+        let both = plain.intersection(&directed);
+        assert_eq!(both.iter().collect::<Vec<_>>(), [a]);
+        assert!(both.has_direction(a, Direction::Left));
+    }
+}
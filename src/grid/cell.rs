@@ -0,0 +1,138 @@
+//! Typed grid cells.
+//!
+//! Puzzles describe their map characters once as a [`Cell`] enum, usually
+//! with [`cell_enum!`](crate::cell_enum), and parse straight into a typed
+//! grid where unknown characters are errors instead of stray matches.
+
+use std::str::FromStr;
+
+use super::{Grid, GridError, Location};
+
+/// A grid cell that is written as a single character.
+pub trait Cell: Copy + Eq {
+    /// The cell written as `c`, or `None` if `c` is not a valid cell.
+    fn from_char(c: char) -> Option<Self>;
+
+    fn to_char(self) -> char;
+}
+
+impl Cell for char {
+    fn from_char(c: char) -> Option<Self> {
+        Some(c)
+    }
+
+    fn to_char(self) -> char {
+        self
+    }
+}
+
+/// Define a fieldless enum whose variants are each written as one character,
+/// implementing [`Cell`], `Display` and the usual derives.
+///
+/// ```
+/// advent_of_code::cell_enum! {
+///     pub enum Tile {
+///         Wall = '#',
+///         Floor = '.',
+///     }
+/// }
+///
+/// let grid: advent_of_code::grid::Grid<Tile> = "#.\n.#".parse().unwrap();
+/// assert_eq!(grid[advent_of_code::grid::Location { row: 0, col: 1 }], Tile::Floor);
+/// assert!("#x".parse::<advent_of_code::grid::Grid<Tile>>().is_err());
+/// ```
+#[macro_export]
+macro_rules! cell_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $( $(#[$variant_meta:meta])* $variant:ident = $c:literal ),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        $vis enum $name {
+            $( $(#[$variant_meta])* $variant ),+
+        }
+
+        impl $crate::grid::Cell for $name {
+            fn from_char(c: char) -> Option<Self> {
+                match c {
+                    $( $c => Some(Self::$variant), )+
+                    _ => None,
+                }
+            }
+
+            fn to_char(self) -> char {
+                match self {
+                    $( Self::$variant => $c ),+
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", $crate::grid::Cell::to_char(*self))
+            }
+        }
+    };
+}
+
+impl<C: Cell> Grid<C> {
+    /// The grid written back out as characters, e.g. for printing or rendering.
+    pub fn to_chars(&self) -> Grid<char> {
+        self.map(|&cell| cell.to_char())
+    }
+}
+
+impl<C: Cell> FromStr for Grid<C> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s
+            .lines()
+            .enumerate()
+            .map(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(col, c)| {
+                        C::from_char(c).ok_or(GridError::InvalidCell {
+                            c,
+                            location: Location { row, col },
+                        })
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+        Grid::from_rows(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::cell_enum! {
+        enum Tile {
+            Wall = '#',
+            Floor = '.',
+            Start = 'S',
+        }
+    }
+
+    #[test]
+    fn test_cell_grid() {
+        let grid: Grid<Tile> = "#S\n.#".parse().unwrap();
+        assert_eq!(grid[Location { row: 0, col: 1 }], Tile::Start);
+        assert_eq!(grid.to_chars().to_string(), "#S\n.#\n");
+        assert_eq!(grid.to_string(), "#S\n.#\n");
+
+        assert_eq!(
+            "#.\n.x".parse::<Grid<Tile>>(),
+            Err(GridError::InvalidCell {
+                c: 'x',
+                location: Location { row: 1, col: 1 },
+            })
+        );
+    }
+}
@@ -0,0 +1,1379 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::num::TryFromIntError;
+use std::ops::{Add, AddAssign, Div, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+
+pub use bits::*;
+pub use cell::*;
+pub use ray::*;
+pub use region::*;
+pub use render::*;
+pub use sparse::*;
+
+mod bits;
+mod cell;
+mod ray;
+mod region;
+mod render;
+mod sparse;
+
+#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone, Ord, PartialOrd)]
+pub struct Location {
+    pub row: usize,
+    pub col: usize,
+}
+
+/// A signed, unbounded coordinate where `x` is the column and `y` is the row.
+///
+/// Unlike [`Location`], a `Point` can leave the map, which makes it suitable
+/// for vector arithmetic and for sparse `HashMap<Point, T>` grids.
+#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone, Ord, PartialOrd, Default)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn chebyshev(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// The four orthogonally adjacent points, in the same order as [`DIRECTIONS`].
+    pub fn neighbors(self) -> [Point; 4] {
+        DIRECTIONS.map(|d| self + Point::from(d))
+    }
+
+    /// Convert to a [`Location`] if the point lies within `height` x `width`.
+    pub fn to_location(self, height: usize, width: usize) -> Option<Location> {
+        Location::try_from(self)
+            .ok()
+            .filter(|location| location.row < height && location.col < width)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, scale: i64) -> Point {
+        Point::new(self.x * scale, self.y * scale)
+    }
+}
+
+impl Div<i64> for Point {
+    type Output = Point;
+
+    fn div(self, divisor: i64) -> Point {
+        Point::new(self.x / divisor, self.y / divisor)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl From<Direction> for Point {
+    fn from(direction: Direction) -> Self {
+        let (row, col) = direction.delta();
+        Point::new(col as i64, row as i64)
+    }
+}
+
+impl From<Direction8> for Point {
+    fn from(direction: Direction8) -> Self {
+        let (row, col) = direction.delta();
+        Point::new(col as i64, row as i64)
+    }
+}
+
+impl From<Location> for Point {
+    fn from(location: Location) -> Self {
+        let x = i64::try_from(location.col).expect("column should fit in i64");
+        let y = i64::try_from(location.row).expect("row should fit in i64");
+        Point::new(x, y)
+    }
+}
+
+impl TryFrom<Point> for Location {
+    type Error = TryFromIntError;
+
+    /// Fails if either coordinate is negative.
+    fn try_from(point: Point) -> Result<Self, Self::Error> {
+        Ok(Location {
+            row: usize::try_from(point.y)?,
+            col: usize::try_from(point.x)?,
+        })
+    }
+}
+
+#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+pub const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+];
+
+/// One of the eight compass directions, including diagonals.
+#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+/// All eight directions in clockwise order, starting from `Up`.
+pub const DIRECTIONS8: [Direction8; 8] = [
+    Direction8::Up,
+    Direction8::UpRight,
+    Direction8::Right,
+    Direction8::DownRight,
+    Direction8::Down,
+    Direction8::DownLeft,
+    Direction8::Left,
+    Direction8::UpLeft,
+];
+
+pub const DIAGONALS: [Direction8; 4] = [
+    Direction8::UpRight,
+    Direction8::DownRight,
+    Direction8::DownLeft,
+    Direction8::UpLeft,
+];
+
+impl Direction {
+    /// The `(row, col)` offset of a single step in this direction.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+        }
+    }
+
+    /// The arrow character used for this direction in puzzle inputs.
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Right => '>',
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+impl Direction8 {
+    /// The `(row, col)` offset of a single step in this direction.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction8::Up => (-1, 0),
+            Direction8::UpRight => (-1, 1),
+            Direction8::Right => (0, 1),
+            Direction8::DownRight => (1, 1),
+            Direction8::Down => (1, 0),
+            Direction8::DownLeft => (1, -1),
+            Direction8::Left => (0, -1),
+            Direction8::UpLeft => (-1, -1),
+        }
+    }
+
+    pub fn opposite(self) -> Direction8 {
+        self.rotate_45().rotate_45().rotate_45().rotate_45()
+    }
+
+    /// Rotate 45 degrees clockwise.
+    pub fn rotate_45(self) -> Direction8 {
+        match self {
+            Direction8::Up => Direction8::UpRight,
+            Direction8::UpRight => Direction8::Right,
+            Direction8::Right => Direction8::DownRight,
+            Direction8::DownRight => Direction8::Down,
+            Direction8::Down => Direction8::DownLeft,
+            Direction8::DownLeft => Direction8::Left,
+            Direction8::Left => Direction8::UpLeft,
+            Direction8::UpLeft => Direction8::Up,
+        }
+    }
+
+    /// Rotate 45 degrees counter-clockwise.
+    pub fn rotate_45_ccw(self) -> Direction8 {
+        match self {
+            Direction8::Up => Direction8::UpLeft,
+            Direction8::UpRight => Direction8::Up,
+            Direction8::Right => Direction8::UpRight,
+            Direction8::DownRight => Direction8::Right,
+            Direction8::Down => Direction8::DownRight,
+            Direction8::DownLeft => Direction8::Down,
+            Direction8::Left => Direction8::DownLeft,
+            Direction8::UpLeft => Direction8::Left,
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        DIAGONALS.contains(&self)
+    }
+
+    /// Combine two perpendicular directions into the diagonal between them.
+    ///
+    /// Returns `None` if the directions are parallel.
+    pub fn between(first: Direction, second: Direction) -> Option<Direction8> {
+        if is_horizontal(first) == is_horizontal(second) {
+            return None;
+        }
+        let (vertical, horizontal) = if is_vertical(first) {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        match (vertical, horizontal) {
+            (Direction::Up, Direction::Right) => Some(Direction8::UpRight),
+            (Direction::Down, Direction::Right) => Some(Direction8::DownRight),
+            (Direction::Down, Direction::Left) => Some(Direction8::DownLeft),
+            (Direction::Up, Direction::Left) => Some(Direction8::UpLeft),
+            _ => None,
+        }
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Direction8::Up,
+            Direction::Down => Direction8::Down,
+            Direction::Left => Direction8::Left,
+            Direction::Right => Direction8::Right,
+        }
+    }
+}
+
+/// An error which can be returned when reading or building a grid from malformed input.
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    /// The character does not appear anywhere in the map.
+    NotFound(char),
+    /// The character was expected once but appears at least twice.
+    Duplicate {
+        c: char,
+        first: Location,
+        second: Location,
+    },
+    /// The character is not one of `^`, `v`, `<` or `>`.
+    InvalidDirection(char),
+    /// A row's width differs from the width of the first row.
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The character does not stand for any kind of cell.
+    InvalidCell { c: char, location: Location },
+}
+
+impl Error for GridError {}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::NotFound(c) => write!(f, "Char '{c}' not found in map"),
+            GridError::Duplicate { c, first, second } => write!(
+                f,
+                "Char '{c}' found more than once in map, at {first:?} and {second:?}"
+            ),
+            GridError::InvalidDirection(c) => write!(f, "Character {c} is not a direction!"),
+            GridError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(f, "row {row} has width {found}, expected {expected}"),
+            GridError::InvalidCell { c, location } => {
+                write!(f, "invalid cell character '{c}' at {location:?}")
+            }
+        }
+    }
+}
+
+pub fn to_direction(c: char) -> Direction {
+    try_to_direction(c).unwrap_or_else(|e| panic!("{e}"))
+}
+
+pub fn try_to_direction(c: char) -> Result<Direction, GridError> {
+    match c {
+        '^' => Ok(Direction::Up),
+        'v' => Ok(Direction::Down),
+        '>' => Ok(Direction::Right),
+        '<' => Ok(Direction::Left),
+        _ => Err(GridError::InvalidDirection(c)),
+    }
+}
+
+pub fn is_horizontal(direction: Direction) -> bool {
+    direction == Direction::Left || direction == Direction::Right
+}
+
+pub fn is_vertical(direction: Direction) -> bool {
+    !is_horizontal(direction)
+}
+
+pub fn rotate_cw(direction: Direction) -> Direction {
+    match direction {
+        Direction::Up => Direction::Right,
+        Direction::Right => Direction::Down,
+        Direction::Down => Direction::Left,
+        Direction::Left => Direction::Up,
+    }
+}
+
+pub fn rotate_ccw(direction: Direction) -> Direction {
+    match direction {
+        Direction::Up => Direction::Left,
+        Direction::Right => Direction::Up,
+        Direction::Down => Direction::Right,
+        Direction::Left => Direction::Down,
+    }
+}
+
+pub fn find_only(map: &[Vec<char>], c: char) -> Location {
+    try_find_only(map, c).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Find the first location of `c`, or [`GridError::NotFound`] if it is absent.
+pub fn try_find_only(map: &[Vec<char>], c: char) -> Result<Location, GridError> {
+    locations_of(map, c).next().ok_or(GridError::NotFound(c))
+}
+
+/// Find the location of `c`, which must appear exactly once in the map.
+pub fn find_unique(map: &[Vec<char>], c: char) -> Result<Location, GridError> {
+    unique(locations_of(map, c), c)
+}
+
+fn locations_of(map: &[Vec<char>], c: char) -> impl Iterator<Item = Location> + '_ {
+    map.iter().enumerate().flat_map(move |(row, row_val)| {
+        row_val
+            .iter()
+            .enumerate()
+            .filter(move |(_, &col_val)| col_val == c)
+            .map(move |(col, _)| Location { row, col })
+    })
+}
+
+fn unique(mut locations: impl Iterator<Item = Location>, c: char) -> Result<Location, GridError> {
+    let first = locations.next().ok_or(GridError::NotFound(c))?;
+    match locations.next() {
+        Some(second) => Err(GridError::Duplicate { c, first, second }),
+        None => Ok(first),
+    }
+}
+
+pub fn create_grid(height: usize, width: usize, value: char) -> Vec<Vec<char>> {
+    vec![vec![value; width]; height]
+}
+
+pub fn at<T: Copy>(map: &[Vec<T>], location: Location) -> T {
+    GridLike::at(map, location)
+}
+
+/// Get the value at `location`, or `None` if it lies outside the map.
+pub fn get<T>(map: &[Vec<T>], location: Location) -> Option<&T> {
+    map.get(location.row)?.get(location.col)
+}
+
+pub fn get_location<T>(
+    map: &[Vec<T>],
+    location: Location,
+    direction: Direction,
+) -> Option<Location> {
+    let (height, width) = dimensions(map);
+    offset(location, direction.delta(), height, width)
+}
+
+/// Like [`get_location`], but also allows diagonal steps.
+pub fn get_location8<T>(
+    map: &[Vec<T>],
+    location: Location,
+    direction: Direction8,
+) -> Option<Location> {
+    let (height, width) = dimensions(map);
+    offset(location, direction.delta(), height, width)
+}
+
+/// Shift `location` by `(row, col)`, staying within `height` x `width`.
+fn offset(
+    location: Location,
+    (row_delta, col_delta): (isize, isize),
+    height: usize,
+    width: usize,
+) -> Option<Location> {
+    let row = location.row.checked_add_signed(row_delta)?;
+    let col = location.col.checked_add_signed(col_delta)?;
+    (row < height && col < width).then_some(Location { row, col })
+}
+
+/// Shift `location` by `(row, col)`, wrapping around the edges of `height` x `width`.
+fn offset_wrapping(
+    location: Location,
+    (row_delta, col_delta): (isize, isize),
+    height: usize,
+    width: usize,
+) -> Location {
+    wrap(
+        Point::from(location) + Point::new(col_delta as i64, row_delta as i64),
+        height,
+        width,
+    )
+}
+
+/// Map any point onto a `height` x `width` torus.
+pub fn wrap(point: Point, height: usize, width: usize) -> Location {
+    Location {
+        row: point.y.rem_euclid(height as i64) as usize,
+        col: point.x.rem_euclid(width as i64) as usize,
+    }
+}
+
+pub fn print_map(map: &[Vec<char>]) {
+    for row in map.iter() {
+        let cols: String = row.iter().collect();
+        println!("{cols}");
+    }
+}
+
+pub fn get_map_counts(map: &[Vec<char>]) -> HashMap<char, i32> {
+    let mut counts: HashMap<char, i32> = HashMap::new();
+    for row in map {
+        for &c in row {
+            *counts.entry(c).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+pub fn print_map_counts(counts: &HashMap<char, i32>) {
+    let mut sorted: Vec<_> = counts.iter().collect();
+    sorted.sort_by_key(|&(k, _)| k);
+    println!("{:?}", sorted);
+}
+
+/// Get all valid neighboring locations (up, down, left, right).
+pub fn neighbors<T>(map: &[Vec<T>], location: Location) -> Vec<Location> {
+    DIRECTIONS
+        .iter()
+        .filter_map(|&d| get_location(map, location, d))
+        .collect()
+}
+
+/// Get all valid neighboring locations, including diagonals.
+pub fn neighbors8<T>(map: &[Vec<T>], location: Location) -> Vec<Location> {
+    DIRECTIONS8
+        .iter()
+        .filter_map(|&d| get_location8(map, location, d))
+        .collect()
+}
+
+/// Get all neighboring locations where the value satisfies the predicate.
+pub fn neighbors_where<T: Copy>(
+    map: &[Vec<T>],
+    location: Location,
+    predicate: impl Fn(T) -> bool,
+) -> Vec<Location> {
+    GridLike::neighbors_where(map, location, predicate)
+}
+
+/// Find all locations in the grid where the value satisfies the predicate.
+pub fn find_all<T: Copy>(map: &[Vec<T>], predicate: impl Fn(T) -> bool) -> Vec<Location> {
+    GridLike::find_all(map, |&value| predicate(value))
+}
+
+/// Get dimensions (height, width) of the grid.
+pub fn dimensions<T>(map: &[Vec<T>]) -> (usize, usize) {
+    if map.is_empty() {
+        (0, 0)
+    } else {
+        (map.len(), map[0].len())
+    }
+}
+
+/// An inclusive, axis-aligned rectangle of locations.
+#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
+pub struct BoundingBox {
+    pub top_left: Location,
+    pub bottom_right: Location,
+}
+
+impl BoundingBox {
+    /// The smallest box containing every location, or `None` if there are none.
+    pub fn from_locations(locations: impl IntoIterator<Item = Location>) -> Option<Self> {
+        let mut locations = locations.into_iter();
+        let first = locations.next()?;
+        Some(locations.fold(
+            BoundingBox {
+                top_left: first,
+                bottom_right: first,
+            },
+            |bbox, location| BoundingBox {
+                top_left: Location {
+                    row: bbox.top_left.row.min(location.row),
+                    col: bbox.top_left.col.min(location.col),
+                },
+                bottom_right: Location {
+                    row: bbox.bottom_right.row.max(location.row),
+                    col: bbox.bottom_right.col.max(location.col),
+                },
+            },
+        ))
+    }
+
+    pub fn height(&self) -> usize {
+        self.bottom_right.row - self.top_left.row + 1
+    }
+
+    pub fn width(&self) -> usize {
+        self.bottom_right.col - self.top_left.col + 1
+    }
+
+    pub fn area(&self) -> usize {
+        self.height() * self.width()
+    }
+
+    pub fn contains(&self, location: Location) -> bool {
+        (self.top_left.row..=self.bottom_right.row).contains(&location.row)
+            && (self.top_left.col..=self.bottom_right.col).contains(&location.col)
+    }
+}
+
+/// A rectangular grid stored as a flat, row-major `Vec<T>`.
+///
+/// Every row has the same width, so lookups are a single index computation
+/// instead of indexing into a row and then a column.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    height: usize,
+    width: usize,
+    topology: Topology,
+}
+
+/// How a [`Grid`] treats steps that leave its edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Topology {
+    /// Steps off the edge go nowhere.
+    #[default]
+    Bounded,
+    /// Steps off one edge come back in on the opposite edge.
+    Torus,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Swap rows and columns.
+    pub fn transpose(&self) -> Self {
+        self.remap(self.width, self.height, |Location { row, col }| Location {
+            row: col,
+            col: row,
+        })
+    }
+
+    /// Rotate a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Self {
+        self.remap(self.width, self.height, |Location { row, col }| Location {
+            row: self.height - 1 - col,
+            col: row,
+        })
+    }
+
+    /// Rotate a quarter turn counter-clockwise.
+    pub fn rotate_ccw(&self) -> Self {
+        self.remap(self.width, self.height, |Location { row, col }| Location {
+            row: col,
+            col: self.width - 1 - row,
+        })
+    }
+
+    /// Mirror left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        self.remap(self.height, self.width, |Location { row, col }| Location {
+            row,
+            col: self.width - 1 - col,
+        })
+    }
+
+    /// Mirror top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        self.remap(self.height, self.width, |Location { row, col }| Location {
+            row: self.height - 1 - row,
+            col,
+        })
+    }
+
+    /// Copy out the cells inside `bbox`, which must lie within the grid.
+    pub fn crop(&self, bbox: BoundingBox) -> Self {
+        assert!(
+            self.contains(bbox.bottom_right),
+            "{bbox:?} out of bounds for {}x{} grid",
+            self.height,
+            self.width
+        );
+        self.remap(bbox.height(), bbox.width(), |Location { row, col }| {
+            Location {
+                row: bbox.top_left.row + row,
+                col: bbox.top_left.col + col,
+            }
+        })
+    }
+
+    /// Surround the grid with a border `n` cells thick filled with `fill`.
+    pub fn pad(&self, n: usize, fill: T) -> Self {
+        let mut padded =
+            Grid::new(self.height + 2 * n, self.width + 2 * n, fill).with_topology(self.topology);
+        for (Location { row, col }, value) in self.cells() {
+            padded[Location {
+                row: row + n,
+                col: col + n,
+            }] = value.clone();
+        }
+        padded
+    }
+
+    /// Replace every cell with `N` cells side by side, widening the grid `N` times.
+    pub fn expand_columns<const N: usize>(&self, expand: impl Fn(&T) -> [T; N]) -> Self {
+        Grid {
+            cells: self.cells.iter().flat_map(expand).collect(),
+            height: self.height,
+            width: self.width * N,
+            topology: self.topology,
+        }
+    }
+
+    /// Build a `height` x `width` grid whose cells are copied from `source(location)`.
+    fn remap(&self, height: usize, width: usize, source: impl Fn(Location) -> Location) -> Self {
+        Grid::from_fn(height, width, |location| self[source(location)].clone())
+            .with_topology(self.topology)
+    }
+
+    /// Create a `height` x `width` grid with every cell set to `value`.
+    pub fn new(height: usize, width: usize, value: T) -> Self {
+        Self {
+            cells: vec![value; height * width],
+            height,
+            width,
+            topology: Topology::Bounded,
+        }
+    }
+
+    /// Copy the grid out into one `Vec` per row.
+    pub fn to_rows(&self) -> Vec<Vec<T>> {
+        self.rows().map(|row| row.to_vec()).collect()
+    }
+}
+
+impl<T> Grid<T> {
+    /// Build a grid from rows, rejecting rows whose width differs from the first.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, GridError> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        let mut cells = Vec::with_capacity(height * width);
+
+        for (row, values) in rows.into_iter().enumerate() {
+            if values.len() != width {
+                return Err(GridError::RaggedRow {
+                    row,
+                    expected: width,
+                    found: values.len(),
+                });
+            }
+            cells.extend(values);
+        }
+
+        Ok(Self {
+            cells,
+            height,
+            width,
+            topology: Topology::Bounded,
+        })
+    }
+
+    /// Create a `height` x `width` grid by calling `f` on every location in row-major order.
+    pub fn from_fn(height: usize, width: usize, mut f: impl FnMut(Location) -> T) -> Self {
+        let cells = (0..height * width)
+            .map(|i| {
+                f(Location {
+                    row: i / width,
+                    col: i % width,
+                })
+            })
+            .collect();
+        Self {
+            cells,
+            height,
+            width,
+            topology: Topology::Bounded,
+        }
+    }
+
+    /// Consume the grid, returning one `Vec` per row.
+    pub fn into_rows(self) -> Vec<Vec<T>> {
+        let mut cells = self.cells.into_iter();
+        (0..self.height)
+            .map(|_| cells.by_ref().take(self.width).collect())
+            .collect()
+    }
+
+    /// Set how steps off the edge of the grid behave.
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Get dimensions (height, width) of the grid.
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    pub fn contains(&self, location: Location) -> bool {
+        location.row < self.height && location.col < self.width
+    }
+
+    pub fn get(&self, location: Location) -> Option<&T> {
+        self.contains(location)
+            .then(|| &self.cells[self.index(location)])
+    }
+
+    pub fn get_mut(&mut self, location: Location) -> Option<&mut T> {
+        if !self.contains(location) {
+            return None;
+        }
+        let index = self.index(location);
+        Some(&mut self.cells[index])
+    }
+
+    /// Move one cell from `location` in `direction`.
+    ///
+    /// On a [`Topology::Bounded`] grid this is `None` if the step leaves the grid;
+    /// on a [`Topology::Torus`] it always succeeds.
+    pub fn get_location(&self, location: Location, direction: Direction) -> Option<Location> {
+        self.step(location, direction.delta())
+    }
+
+    /// Like [`Grid::get_location`], but also allows diagonal steps.
+    pub fn get_location8(&self, location: Location, direction: Direction8) -> Option<Location> {
+        self.step(location, direction.delta())
+    }
+
+    /// Move one cell from `location` in `direction`, wrapping around the edges
+    /// regardless of the grid's topology.
+    pub fn get_location_wrapping(&self, location: Location, direction: Direction) -> Location {
+        offset_wrapping(location, direction.delta(), self.height, self.width)
+    }
+
+    /// Map a point that may lie outside the grid back onto it, as if the grid were a torus.
+    pub fn wrap(&self, point: Point) -> Location {
+        wrap(point, self.height, self.width)
+    }
+
+    fn step(&self, location: Location, delta: (isize, isize)) -> Option<Location> {
+        match self.topology {
+            Topology::Bounded => offset(location, delta, self.height, self.width),
+            Topology::Torus => Some(offset_wrapping(location, delta, self.height, self.width)),
+        }
+    }
+
+    /// Get all valid neighboring locations (up, down, left, right).
+    pub fn neighbors(&self, location: Location) -> Vec<Location> {
+        DIRECTIONS
+            .iter()
+            .filter_map(|&d| self.get_location(location, d))
+            .collect()
+    }
+
+    /// Get all valid neighboring locations, including diagonals.
+    pub fn neighbors8(&self, location: Location) -> Vec<Location> {
+        DIRECTIONS8
+            .iter()
+            .filter_map(|&d| self.get_location8(location, d))
+            .collect()
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    /// Iterate over the rows, one (possibly empty) slice for each of the
+    /// grid's `height` rows, like [`Grid::into_rows`].
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |row| self.row(row))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column {col} out of bounds");
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |col| self.column(col))
+    }
+
+    /// Iterate over every location in row-major order.
+    pub fn locations(&self) -> impl Iterator<Item = Location> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| Location { row, col }))
+    }
+
+    /// Iterate over every cell along with its location, in row-major order.
+    pub fn cells(&self) -> impl Iterator<Item = (Location, &T)> {
+        self.locations().zip(self.cells.iter())
+    }
+
+    /// Build a new grid of the same shape by applying `f` to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            height: self.height,
+            width: self.width,
+            topology: self.topology,
+        }
+    }
+
+    /// Find all locations in the grid where the value satisfies the predicate.
+    pub fn find_all(&self, predicate: impl Fn(&T) -> bool) -> Vec<Location> {
+        GridLike::find_all(self, predicate)
+    }
+
+    /// Find the first location, in row-major order, holding `value`.
+    pub fn position(&self, value: &T) -> Option<Location>
+    where
+        T: PartialEq,
+    {
+        self.cells()
+            .find(|(_, v)| *v == value)
+            .map(|(location, _)| location)
+    }
+
+    #[inline]
+    fn index(&self, location: Location) -> usize {
+        location.row * self.width + location.col
+    }
+
+    #[inline]
+    fn location(&self, index: usize) -> Location {
+        Location {
+            row: index / self.width,
+            col: index % self.width,
+        }
+    }
+}
+
+impl<T: Copy> Grid<T> {
+    pub fn at(&self, location: Location) -> T {
+        GridLike::at(self, location)
+    }
+
+    /// Get all neighboring locations where the value satisfies the predicate.
+    pub fn neighbors_where(
+        &self,
+        location: Location,
+        predicate: impl Fn(T) -> bool,
+    ) -> Vec<Location> {
+        GridLike::neighbors_where(self, location, predicate)
+    }
+}
+
+impl<C: Cell> Grid<C> {
+    pub fn find_only(&self, cell: C) -> Location {
+        self.try_find_only(cell).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Find the first location of `cell`, or [`GridError::NotFound`] if it is absent.
+    pub fn try_find_only(&self, cell: C) -> Result<Location, GridError> {
+        self.position(&cell)
+            .ok_or(GridError::NotFound(cell.to_char()))
+    }
+
+    /// Find the location of `cell`, which must appear exactly once in the grid.
+    pub fn find_unique(&self, cell: C) -> Result<Location, GridError> {
+        unique(
+            self.cells()
+                .filter(|(_, &value)| value == cell)
+                .map(|(location, _)| location),
+            cell.to_char(),
+        )
+    }
+
+    pub fn print(&self) {
+        print!("{}", self.to_chars());
+    }
+}
+
+/// Cell storage shared by the dense [`Grid`], the hash-backed [`SparseGrid`]
+/// and plain `Vec`-of-rows maps.
+///
+/// Lets code that only reads and writes individual cells work on any of them.
+pub trait GridLike<T> {
+    /// The value at `location`, or `None` if the grid cannot hold it.
+    fn get(&self, location: Location) -> Option<&T>;
+
+    /// The value at `location`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the grid cannot hold `location`.
+    fn at(&self, location: Location) -> T
+    where
+        T: Copy,
+    {
+        match self.get(location) {
+            Some(&value) => value,
+            None => panic!(
+                "{location:?} out of bounds for {:?} grid",
+                self.dimensions()
+            ),
+        }
+    }
+
+    /// One step from `location` in `direction`, or `None` if it leaves the grid.
+    fn step(&self, location: Location, direction: Direction) -> Option<Location> {
+        let (height, width) = self.dimensions();
+        offset(location, direction.delta(), height, width)
+    }
+
+    /// Get all neighboring locations where the value satisfies the predicate.
+    fn neighbors_where(&self, location: Location, predicate: impl Fn(T) -> bool) -> Vec<Location>
+    where
+        T: Copy,
+    {
+        DIRECTIONS
+            .iter()
+            .filter_map(|&d| self.step(location, d))
+            .filter(|&next| predicate(self.at(next)))
+            .collect()
+    }
+
+    /// Store `value` at `location`.
+    fn set(&mut self, location: Location, value: T);
+
+    /// (height, width) of the area from the origin that covers every stored cell.
+    fn dimensions(&self) -> (usize, usize);
+
+    /// The smallest box holding every stored cell, or `None` if there are none.
+    fn bounding_box(&self) -> Option<BoundingBox>;
+
+    /// Every stored cell in row-major order.
+    fn entries<'a>(&'a self) -> impl Iterator<Item = (Location, &'a T)>
+    where
+        T: 'a;
+
+    /// Locations of all stored cells whose value matches, in row-major order.
+    fn find_all(&self, predicate: impl Fn(&T) -> bool) -> Vec<Location> {
+        self.entries()
+            .filter(|(_, value)| predicate(value))
+            .map(|(location, _)| location)
+            .collect()
+    }
+}
+
+impl<T> GridLike<T> for Grid<T> {
+    fn get(&self, location: Location) -> Option<&T> {
+        Grid::get(self, location)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `location` is outside the grid.
+    fn set(&mut self, location: Location, value: T) {
+        self[location] = value;
+    }
+
+    /// Follows the grid's [`Topology`].
+    fn step(&self, location: Location, direction: Direction) -> Option<Location> {
+        self.get_location(location, direction)
+    }
+
+    fn dimensions(&self) -> (usize, usize) {
+        Grid::dimensions(self)
+    }
+
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        (self.height > 0 && self.width > 0).then(|| BoundingBox {
+            top_left: Location { row: 0, col: 0 },
+            bottom_right: Location {
+                row: self.height - 1,
+                col: self.width - 1,
+            },
+        })
+    }
+
+    fn entries<'a>(&'a self) -> impl Iterator<Item = (Location, &'a T)>
+    where
+        T: 'a,
+    {
+        self.cells()
+    }
+}
+
+/// Maps kept as one `Vec` per row, which must all be the same width.
+impl<T> GridLike<T> for [Vec<T>] {
+    fn get(&self, location: Location) -> Option<&T> {
+        get(self, location)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `location` is outside the map.
+    fn set(&mut self, location: Location, value: T) {
+        self[location.row][location.col] = value;
+    }
+
+    fn dimensions(&self) -> (usize, usize) {
+        dimensions(self)
+    }
+
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        let (height, width) = dimensions(self);
+        (height > 0 && width > 0).then(|| BoundingBox {
+            top_left: Location { row: 0, col: 0 },
+            bottom_right: Location {
+                row: height - 1,
+                col: width - 1,
+            },
+        })
+    }
+
+    fn entries<'a>(&'a self) -> impl Iterator<Item = (Location, &'a T)>
+    where
+        T: 'a,
+    {
+        self.iter().enumerate().flat_map(|(row, values)| {
+            values
+                .iter()
+                .enumerate()
+                .map(move |(col, value)| (Location { row, col }, value))
+        })
+    }
+}
+
+impl<T> Index<Location> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, location: Location) -> &Self::Output {
+        assert!(
+            self.contains(location),
+            "{location:?} out of bounds for {}x{} grid",
+            self.height,
+            self.width
+        );
+        &self.cells[Grid::index(self, location)]
+    }
+}
+
+impl<T> IndexMut<Location> for Grid<T> {
+    fn index_mut(&mut self, location: Location) -> &mut Self::Output {
+        assert!(
+            self.contains(location),
+            "{location:?} out of bounds for {}x{} grid",
+            self.height,
+            self.width
+        );
+        let index = Grid::index(self, location);
+        &mut self.cells[index]
+    }
+}
+
+impl<T> TryFrom<Vec<Vec<T>>> for Grid<T> {
+    type Error = GridError;
+
+    fn try_from(rows: Vec<Vec<T>>) -> Result<Self, Self::Error> {
+        Grid::from_rows(rows)
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for value in row {
+                write!(f, "{value}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_from_str() {
+        let grid: Grid<char> = "ab\ncd\nef".parse().unwrap();
+        assert_eq!(grid.dimensions(), (3, 2));
+        assert_eq!(grid[Location { row: 2, col: 1 }], 'f');
+        assert_eq!(grid.to_string(), "ab\ncd\nef\n");
+    }
+
+    #[test]
+    fn test_grid_rejects_ragged_rows() {
+        let result = "abc\nd\nefg".parse::<Grid<char>>();
+        assert_eq!(
+            result,
+            Err(GridError::RaggedRow {
+                row: 1,
+                expected: 3,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_grid_rows_and_columns() {
+        let grid: Grid<char> = "abc\ndef".parse().unwrap();
+        let rows: Vec<String> = grid.rows().map(|row| row.iter().collect()).collect();
+        assert_eq!(rows, ["abc", "def"]);
+        let columns: Vec<String> = grid.columns().map(|col| col.collect()).collect();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+        assert_eq!(grid.clone().into_rows(), grid.to_rows());
+
+        let empty: Grid<char> = Grid::new(2, 0, '.');
+        assert_eq!(empty.rows().count(), 2);
+        assert_eq!(empty.clone().into_rows(), empty.to_rows());
+    }
+
+    #[test]
+    fn test_vec_rows_match_grid() {
+        let rows = vec![vec![1, 2, 3], vec![2, 9, 4]];
+        let grid = Grid::from_rows(rows.clone()).unwrap();
+        let center = Location { row: 0, col: 1 };
+        assert_eq!(at(&rows, center), grid.at(center));
+        assert_eq!(
+            neighbors_where(&rows, center, |v| v > 2),
+            grid.neighbors_where(center, |v| v > 2)
+        );
+        assert_eq!(find_all(&rows, |v| v == 2), grid.find_all(|&v| v == 2));
+    }
+
+    #[test]
+    fn test_grid_get_location() {
+        let grid = Grid::new(2, 3, '.');
+        let corner = Location { row: 1, col: 2 };
+        assert_eq!(grid.get_location(corner, Direction::Right), None);
+        assert_eq!(grid.get_location(corner, Direction::Down), None);
+        assert_eq!(
+            grid.get_location(corner, Direction::Up),
+            Some(Location { row: 0, col: 2 })
+        );
+        assert_eq!(grid.neighbors(corner).len(), 2);
+        assert_eq!(grid.get(Location { row: 2, col: 0 }), None);
+    }
+
+    #[test]
+    fn test_grid_transformations() {
+        let grid: Grid<char> = "abc\ndef".parse().unwrap();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc\n");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(
+            grid.rotate_cw().rotate_cw(),
+            grid.flip_vertical().flip_horizontal()
+        );
+        assert_eq!(grid.pad(1, '.').to_string(), ".....\n.abc.\n.def.\n.....\n");
+        assert_eq!(
+            grid.expand_columns(|&c| [c, c.to_ascii_uppercase()])
+                .to_string(),
+            "aAbBcC\ndDeEfF\n"
+        );
+
+        let bbox =
+            BoundingBox::from_locations([Location { row: 1, col: 2 }, Location { row: 0, col: 1 }])
+                .unwrap();
+        assert_eq!((bbox.height(), bbox.width(), bbox.area()), (2, 2, 4));
+        assert_eq!(grid.crop(bbox).to_string(), "bc\nef\n");
+        assert_eq!(
+            grid.pad(2, '.').crop(BoundingBox {
+                top_left: Location { row: 2, col: 2 },
+                bottom_right: Location { row: 3, col: 4 },
+            }),
+            grid
+        );
+    }
+
+    #[test]
+    fn test_fallible_lookups() {
+        let rows = vec![vec!['S', '.'], vec!['.', 'S']];
+        assert_eq!(try_find_only(&rows, 'S'), Ok(Location { row: 0, col: 0 }));
+        assert_eq!(try_find_only(&rows, 'E'), Err(GridError::NotFound('E')));
+        assert_eq!(
+            find_unique(&rows, 'S'),
+            Err(GridError::Duplicate {
+                c: 'S',
+                first: Location { row: 0, col: 0 },
+                second: Location { row: 1, col: 1 },
+            })
+        );
+        assert_eq!(get(&rows, Location { row: 1, col: 1 }), Some(&'S'));
+        assert_eq!(get(&rows, Location { row: 1, col: 2 }), None);
+
+        let grid = Grid::from_rows(rows).unwrap();
+        assert_eq!(grid.find_unique('.').map_err(|e| e.to_string()), Err("Char '.' found more than once in map, at Location { row: 0, col: 1 } and Location { row: 1, col: 0 }".to_string()));
+        assert_eq!(try_to_direction('x'), Err(GridError::InvalidDirection('x')));
+        assert_eq!(try_to_direction('<'), Ok(Direction::Left));
+    }
+
+    #[test]
+    fn test_torus_topology() {
+        let grid = Grid::new(3, 4, '.').with_topology(Topology::Torus);
+        let corner = Location { row: 0, col: 3 };
+        assert_eq!(
+            grid.get_location(corner, Direction::Up),
+            Some(Location { row: 2, col: 3 })
+        );
+        assert_eq!(
+            grid.get_location8(corner, Direction8::UpRight),
+            Some(Location { row: 2, col: 0 })
+        );
+        assert_eq!(grid.neighbors(corner).len(), 4);
+
+        let bounded = Grid::new(3, 4, '.');
+        assert_eq!(bounded.get_location(corner, Direction::Right), None);
+        assert_eq!(
+            bounded.get_location_wrapping(corner, Direction::Right),
+            Location { row: 0, col: 0 }
+        );
+        assert_eq!(bounded.wrap(Point::new(-9, 7)), Location { row: 1, col: 3 });
+    }
+
+    #[test]
+    fn test_point_arithmetic() {
+        let a = Point::new(3, -4);
+        let b = Point::new(-1, 2);
+        assert_eq!(a + b, Point::new(2, -2));
+        assert_eq!(a - b, Point::new(4, -6));
+        assert_eq!(b * 3, Point::new(-3, 6));
+        assert_eq!(-a, Point::new(-3, 4));
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!(
+            Point::ORIGIN + Point::from(Direction::Up),
+            Point::new(0, -1)
+        );
+    }
+
+    #[test]
+    fn test_point_location_conversion() {
+        let location = Location { row: 2, col: 5 };
+        let point = Point::from(location);
+        assert_eq!(point, Point::new(5, 2));
+        assert_eq!(Location::try_from(point), Ok(location));
+        assert!(Location::try_from(Point::new(-1, 0)).is_err());
+        assert_eq!(point.to_location(3, 6), Some(location));
+        assert_eq!(point.to_location(2, 6), None);
+    }
+
+    #[test]
+    fn test_direction8() {
+        for d in DIRECTIONS8 {
+            let (row, col) = d.delta();
+            let (opposite_row, opposite_col) = d.opposite().delta();
+            assert_eq!((row + opposite_row, col + opposite_col), (0, 0));
+            assert_eq!(d.rotate_45().rotate_45_ccw(), d);
+            assert_eq!(d.is_diagonal(), row != 0 && col != 0);
+        }
+        for d in DIRECTIONS {
+            assert_eq!(Direction8::from(d).delta(), d.delta());
+            assert_eq!(
+                Direction8::from(d.opposite()),
+                Direction8::from(d).opposite()
+            );
+        }
+        assert_eq!(
+            Direction8::between(Direction::Left, Direction::Down),
+            Some(Direction8::DownLeft)
+        );
+        assert_eq!(Direction8::between(Direction::Left, Direction::Right), None);
+    }
+
+    #[test]
+    fn test_neighbors8() {
+        let grid = Grid::new(3, 3, '.');
+        assert_eq!(grid.neighbors8(Location { row: 1, col: 1 }).len(), 8);
+        assert_eq!(grid.neighbors8(Location { row: 0, col: 0 }).len(), 3);
+        assert_eq!(
+            grid.get_location8(Location { row: 0, col: 2 }, Direction8::DownLeft),
+            Some(Location { row: 1, col: 1 })
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! Straight-line walks across grids.
+//!
+//! Rays step by a fixed offset until they leave the grid, and lines join two
+//! points with Bresenham's algorithm, which is enough for line-of-sight checks.
+
+use super::{BitGrid, Grid, Location, Point};
+
+/// Cells from a starting point onwards, stepping by a fixed offset and
+/// stopping at the first step outside the grid.
+#[derive(Debug, Clone)]
+pub struct Ray {
+    next: Point,
+    delta: Point,
+    height: usize,
+    width: usize,
+}
+
+impl Iterator for Ray {
+    type Item = Location;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let location = self.next.to_location(self.height, self.width)?;
+        self.next += self.delta;
+        Some(location)
+    }
+}
+
+/// Walk from `from` by `delta` inside a `height` x `width` grid, starting with
+/// `from` itself.
+///
+/// The ray is empty when `from` is outside the grid. A zero `delta` repeats
+/// `from` forever, so bound it with `take`.
+pub fn ray(from: Point, delta: Point, height: usize, width: usize) -> Ray {
+    Ray {
+        next: from,
+        delta,
+        height,
+        width,
+    }
+}
+
+/// Every point on the straight line from `a` to `b`, both included, using
+/// Bresenham's algorithm.
+pub fn line(a: Point, b: Point) -> Vec<Point> {
+    let dx = (b.x - a.x).abs();
+    let dy = -(b.y - a.y).abs();
+    let step = Point::new((b.x - a.x).signum(), (b.y - a.y).signum());
+
+    let mut result = Vec::with_capacity(dx.max(-dy) as usize + 1);
+    let mut current = a;
+    let mut error = dx + dy;
+    loop {
+        result.push(current);
+        if current == b {
+            break;
+        }
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            current.x += step.x;
+        }
+        if doubled <= dx {
+            error += dx;
+            current.y += step.y;
+        }
+    }
+    result
+}
+
+impl<T> Grid<T> {
+    /// Like [`ray`], bounded by this grid.
+    pub fn ray(&self, from: Location, delta: Point) -> Ray {
+        ray(Point::from(from), delta, self.height, self.width)
+    }
+
+    /// Cells that can be seen from `from`, which is always included.
+    ///
+    /// A cell is visible when no cell strictly between it and `from` on the
+    /// [`line`] joining them is a `blocker`, so blocking cells themselves can
+    /// still be seen.
+    pub fn visible_from(&self, from: Location, blocker: impl Fn(&T) -> bool) -> BitGrid {
+        let origin = Point::from(from);
+        let mut result = BitGrid::new(self.height, self.width);
+        for (location, _) in self.cells() {
+            let points = line(origin, Point::from(location));
+            let inner = points.get(1..points.len() - 1).unwrap_or_default();
+            let blocked = inner.iter().any(|&p| {
+                let between = Location::try_from(p).expect("line should stay inside the grid");
+                blocker(&self[between])
+            });
+            if !blocked {
+                result.insert(location);
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ray() {
+        let cells: Vec<Location> = ray(Point::new(1, 0), Point::new(1, 1), 3, 4).collect();
+        assert_eq!(
+            cells,
+            [
+                Location { row: 0, col: 1 },
+                Location { row: 1, col: 2 },
+                Location { row: 2, col: 3 },
+            ]
+        );
+        assert_eq!(ray(Point::new(-1, 0), Point::new(1, 0), 3, 4).count(), 0);
+    }
+
+    #[test]
+    fn test_line() {
+        assert_eq!(
+            line(Point::new(0, 0), Point::new(4, 2)),
+            [
+                Point::new(0, 0),
+                Point::new(1, 1),
+                Point::new(2, 1),
+                Point::new(3, 2),
+                Point::new(4, 2),
+            ]
+        );
+        let back = line(Point::new(4, 2), Point::new(0, 0));
+        assert_eq!(back.len(), 5);
+        assert_eq!(back.first(), Some(&Point::new(4, 2)));
+        assert_eq!(line(Point::new(2, 2), Point::new(2, 2)), [Point::new(2, 2)]);
+    }
+
+    #[test]
+    fn test_visible_from() {
+        let grid: Grid<char> = "....\n.#..\n....".parse().unwrap();
+        let visible = grid.visible_from(Location { row: 0, col: 0 }, |&c| c == '#');
+        assert!(visible.is_set(Location { row: 1, col: 1 }));
+        assert!(!visible.is_set(Location { row: 2, col: 2 }));
+        assert!(visible.is_set(Location { row: 1, col: 3 }));
+        assert!(!visible.is_set(Location { row: 2, col: 3 }));
+        assert_eq!(visible.count_ones(), 8);
+    }
+}
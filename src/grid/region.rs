@@ -0,0 +1,314 @@
+//! Connected-component labelling for grids.
+//!
+//! Splits a grid into regions of 4-connected cells and measures each
+//! region's area, perimeter, sides and holes.
+
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use super::{
+    offset, rotate_cw, BitGrid, BoundingBox, Direction8, Grid, GridLike, Location, DIRECTIONS,
+    DIRECTIONS8,
+};
+
+/// A set of orthogonally connected cells that all belong together.
+///
+/// Regions from the same call share one label grid, so outline measurements
+/// are only paid for when asked for.
+#[derive(Debug, Clone)]
+pub struct Region {
+    id: usize,
+    cells: Vec<Location>,
+    bounding_box: BoundingBox,
+    labels: Rc<Grid<usize>>,
+}
+
+impl Region {
+    /// The region's cells in row-major order.
+    pub fn cells(&self) -> &[Location] {
+        &self.cells
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Number of cell edges that border a different region or the edge of the grid.
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|&location| {
+                DIRECTIONS
+                    .iter()
+                    .filter(|&&d| !self.contains_opt(self.labels.get_location(location, d)))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Number of convex and concave corners along the region's outline, holes included.
+    pub fn corners(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|&location| self.corners_at(location))
+            .sum()
+    }
+
+    /// Number of straight fence sections around the region, which always equals
+    /// the number of corners.
+    pub fn sides(&self) -> usize {
+        self.corners()
+    }
+
+    pub fn bounding_box(&self) -> BoundingBox {
+        self.bounding_box
+    }
+
+    /// Count the enclosed pockets of other cells that the region fully surrounds.
+    pub fn holes(&self) -> usize {
+        // Work in the bounding box padded by one cell on every side, shifted so
+        // the padding starts at the origin.
+        let top_left = self.bounding_box.top_left;
+        let height = self.bounding_box.height() + 2;
+        let width = self.bounding_box.width() + 2;
+        let mut seen = BitGrid::new(height, width);
+        for cell in &self.cells {
+            seen.insert(Location {
+                row: cell.row - top_left.row + 1,
+                col: cell.col - top_left.col + 1,
+            });
+        }
+
+        // A 4-connected region can be escaped diagonally, so its complement is
+        // 8-connected. The padded border is always one outside component; every
+        // other component is a hole.
+        let mut components = 0;
+        let mut queue = VecDeque::new();
+        for start in (0..height).flat_map(|row| (0..width).map(move |col| Location { row, col })) {
+            if !seen.insert(start) {
+                continue;
+            }
+            components += 1;
+            queue.push_back(start);
+            while let Some(current) = queue.pop_front() {
+                for d in DIRECTIONS8 {
+                    let Some(next) = offset(current, d.delta(), height, width) else {
+                        continue;
+                    };
+                    if seen.insert(next) {
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+        components - 1
+    }
+
+    pub fn contains(&self, location: Location) -> bool {
+        self.labels.get(location) == Some(&self.id)
+    }
+
+    fn contains_opt(&self, location: Option<Location>) -> bool {
+        location.is_some_and(|location| self.labels[location] == self.id)
+    }
+
+    /// Count the outline corners touching a single cell, checking each pair of
+    /// adjacent sides and the diagonal between them.
+    fn corners_at(&self, location: Location) -> usize {
+        DIRECTIONS
+            .iter()
+            .filter(|&&first_direction| {
+                let second_direction = rotate_cw(first_direction);
+                let diagonal = Direction8::between(first_direction, second_direction)
+                    .expect("rotated directions should be perpendicular");
+
+                let first = self.contains_opt(self.labels.get_location(location, first_direction));
+                let second =
+                    self.contains_opt(self.labels.get_location(location, second_direction));
+                let diagonal = self.contains_opt(self.labels.get_location8(location, diagonal));
+
+                // Outer corner when neither side is in the region, inner corner when
+                // both sides are but the cell between them is not.
+                (!first && !second) || (first && second && !diagonal)
+            })
+            .count()
+    }
+}
+
+impl PartialEq for Region {
+    fn eq(&self, other: &Self) -> bool {
+        self.cells == other.cells
+    }
+}
+
+impl Eq for Region {}
+
+/// Label for cells that have not been assigned a region yet.
+const UNLABELLED: usize = usize::MAX;
+/// Label for cells that were filtered out and belong to no region.
+const EXCLUDED: usize = usize::MAX - 1;
+
+/// Split the grid into regions of orthogonally adjacent cells.
+///
+/// Two neighbouring cells are in the same region when `same_region` returns
+/// true for their values. Regions are returned in row-major order of their
+/// first cell.
+pub fn connected_components<T: Copy>(
+    map: &impl GridLike<T>,
+    same_region: impl Fn(T, T) -> bool,
+) -> Vec<Region> {
+    connected_components_where(map, |_| true, same_region)
+}
+
+/// Like [`connected_components`], but only stored cells whose value
+/// satisfies `include` are grouped into regions; every other cell is treated
+/// as background. Only the included cells are ever visited, which suits
+/// sparse grids.
+pub fn connected_components_where<T: Copy>(
+    map: &impl GridLike<T>,
+    include: impl Fn(T) -> bool,
+    same_region: impl Fn(T, T) -> bool,
+) -> Vec<Region> {
+    let labels = Rc::new(label(map, include, same_region));
+    let mut regions: Vec<Region> = Vec::new();
+
+    for (index, &id) in labels.cells.iter().enumerate() {
+        if id == EXCLUDED {
+            continue;
+        }
+        let location = labels.location(index);
+        if id == regions.len() {
+            regions.push(Region {
+                id,
+                cells: Vec::new(),
+                bounding_box: BoundingBox {
+                    top_left: location,
+                    bottom_right: location,
+                },
+                labels: Rc::clone(&labels),
+            });
+        }
+        let region = &mut regions[id];
+        region.cells.push(location);
+        region.bounding_box = BoundingBox {
+            top_left: Location {
+                row: region.bounding_box.top_left.row.min(location.row),
+                col: region.bounding_box.top_left.col.min(location.col),
+            },
+            bottom_right: Location {
+                row: region.bounding_box.bottom_right.row.max(location.row),
+                col: region.bounding_box.bottom_right.col.max(location.col),
+            },
+        };
+    }
+
+    regions
+}
+
+/// Give every cell the id of its region, numbering regions in row-major order.
+fn label<T: Copy>(
+    map: &impl GridLike<T>,
+    include: impl Fn(T) -> bool,
+    same_region: impl Fn(T, T) -> bool,
+) -> Grid<usize> {
+    let (height, width) = map.dimensions();
+    let mut labels = Grid::new(height, width, EXCLUDED);
+    let seeds = map.find_all(|&value| include(value));
+    for &location in &seeds {
+        labels[location] = UNLABELLED;
+    }
+
+    let value = |location| *map.get(location).expect("labelled cells are in the map");
+    let mut next_id = 0;
+    let mut queue = VecDeque::new();
+    for start in seeds {
+        if labels[start] != UNLABELLED {
+            continue;
+        }
+        labels[start] = next_id;
+        queue.push_back(start);
+        while let Some(current) = queue.pop_front() {
+            for &d in DIRECTIONS.iter() {
+                let Some(next) = labels.get_location(current, d) else {
+                    continue;
+                };
+                if labels[next] == UNLABELLED && same_region(value(current), value(next)) {
+                    labels[next] = next_id;
+                    queue.push_back(next);
+                }
+            }
+        }
+        next_id += 1;
+    }
+
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::SparseGrid;
+
+    #[test]
+    fn test_connected_components() {
+        let grid: Grid<char> = "AAAA\nBBCD\nBBCC\nEEEC".parse().unwrap();
+        let regions = connected_components(&grid, |a, b| a == b);
+        let summary: Vec<(char, usize, usize, usize)> = regions
+            .iter()
+            .map(|r| (grid[r.cells()[0]], r.area(), r.perimeter(), r.sides()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ('A', 4, 10, 4),
+                ('B', 4, 8, 4),
+                ('C', 4, 10, 8),
+                ('D', 1, 4, 4),
+                ('E', 3, 8, 4),
+            ]
+        );
+        let vowels = connected_components_where(&grid, |c| "AE".contains(c), |a, b| a == b);
+        assert_eq!(vowels.len(), 2);
+        assert_eq!(vowels[1].cells()[0], Location { row: 3, col: 0 });
+        let sizes: Vec<usize> = vowels.iter().map(Region::area).collect();
+        assert_eq!(sizes, [4, 3]);
+        // The two arms of the U only meet on the bottom row.
+        let u: Grid<char> = "#.#\n#.#\n###".parse().unwrap();
+        let walls = connected_components_where(&u, |c| c == '#', |_, _| true);
+        assert_eq!(walls.len(), 1);
+
+        let mut sparse = SparseGrid::with_dimensions(4, 4, '.');
+        for location in grid.find_all(|&c| c == 'E') {
+            GridLike::set(&mut sparse, location, 'E');
+        }
+        let letters = connected_components_where(&sparse, |c| c == 'E', |a, b| a == b);
+        assert_eq!(letters.len(), 1);
+        assert_eq!(letters[0].area(), 3);
+
+        assert_eq!(
+            regions[2].bounding_box(),
+            BoundingBox {
+                top_left: Location { row: 1, col: 2 },
+                bottom_right: Location { row: 3, col: 3 },
+            }
+        );
+    }
+
+    #[test]
+    fn test_region_holes() {
+        let grid: Grid<char> = "OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO".parse().unwrap();
+        let regions = connected_components(&grid, |a, b| a == b);
+        assert_eq!(regions.len(), 5);
+        assert_eq!(regions[0].holes(), 4);
+        assert_eq!(regions[0].sides(), 20);
+        assert_eq!(regions[1].holes(), 0);
+        assert!(regions[0].contains(Location { row: 0, col: 0 }));
+        assert!(!regions[0].contains(Location { row: 1, col: 1 }));
+
+        // A diagonal gap in the ring lets the inside escape.
+        let grid: Grid<char> = "XXX\nX.X\nXX.".parse().unwrap();
+        let regions = connected_components(&grid, |a, b| a == b);
+        assert_eq!(regions[0].area(), 7);
+        assert_eq!(regions[0].holes(), 0);
+    }
+}
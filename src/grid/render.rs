@@ -0,0 +1,171 @@
+//! Annotated terminal rendering for character grids.
+//!
+//! Draws a map with paths, highlighted cells and per-character colours
+//! layered on top, either with ANSI escape codes or as plain text.
+
+use std::collections::HashMap;
+
+use super::{Grid, Location, DIRECTIONS};
+use crate::template::ANSI_RESET;
+
+/// Builds up layers to draw on top of a map.
+///
+/// Later layers win over earlier ones where they overlap, and any layer wins
+/// over the per-character palette.
+#[derive(Debug, Clone, Default)]
+pub struct Renderer {
+    palette: HashMap<char, String>,
+    overlays: HashMap<Location, Overlay>,
+    plain: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Overlay {
+    c: Option<char>,
+    style: String,
+}
+
+impl Renderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Draw every occurrence of `c` in `style`.
+    pub fn color(mut self, c: char, style: &str) -> Self {
+        self.palette.insert(c, style.to_string());
+        self
+    }
+
+    /// Draw the given cells in `style`, keeping their characters.
+    pub fn highlight(self, locations: impl IntoIterator<Item = Location>, style: &str) -> Self {
+        self.overlay(locations.into_iter().map(|l| (l, None)), style)
+    }
+
+    /// Replace the given cells with `c`, drawn in `style`.
+    pub fn mark(self, locations: impl IntoIterator<Item = Location>, c: char, style: &str) -> Self {
+        self.overlay(locations.into_iter().map(|l| (l, Some(c))), style)
+    }
+
+    /// Draw a path as arrows pointing from each cell to the next, in `style`.
+    ///
+    /// Steps between cells that are not adjacent keep the original character.
+    pub fn path(self, path: &[Location], style: &str) -> Self {
+        let arrows = path
+            .windows(2)
+            .map(|step| (step[0], direction_between(step[0], step[1])))
+            .chain(path.last().map(|&l| (l, None)));
+        self.overlay(arrows, style)
+    }
+
+    /// Leave out all escape codes, which keeps the output readable in tests and logs.
+    pub fn plain(mut self) -> Self {
+        self.plain = true;
+        self
+    }
+
+    pub fn render(&self, map: &Grid<char>) -> String {
+        let mut result = String::new();
+
+        for (row, values) in map.rows().enumerate() {
+            let mut active = "";
+            for (col, &value) in values.iter().enumerate() {
+                let overlay = self.overlays.get(&Location { row, col });
+                let c = overlay.and_then(|o| o.c).unwrap_or(value);
+                let style = match overlay {
+                    Some(o) => o.style.as_str(),
+                    None => self.palette.get(&value).map_or("", |s| s.as_str()),
+                };
+
+                if !self.plain && style != active {
+                    if !active.is_empty() {
+                        result.push_str(ANSI_RESET);
+                    }
+                    result.push_str(style);
+                    active = style;
+                }
+                result.push(c);
+            }
+            if !self.plain && !active.is_empty() {
+                result.push_str(ANSI_RESET);
+            }
+            result.push('\n');
+        }
+
+        result
+    }
+
+    pub fn print(&self, map: &Grid<char>) {
+        print!("{}", self.render(map));
+    }
+
+    fn overlay(
+        mut self,
+        cells: impl IntoIterator<Item = (Location, Option<char>)>,
+        style: &str,
+    ) -> Self {
+        for (location, c) in cells {
+            self.overlays.insert(
+                location,
+                Overlay {
+                    c,
+                    style: style.to_string(),
+                },
+            );
+        }
+        self
+    }
+}
+
+/// The arrow pointing from `from` to an orthogonally adjacent `to`.
+fn direction_between(from: Location, to: Location) -> Option<char> {
+    DIRECTIONS
+        .iter()
+        .find(|d| {
+            let (row, col) = d.delta();
+            from.row.checked_add_signed(row) == Some(to.row)
+                && from.col.checked_add_signed(col) == Some(to.col)
+        })
+        .map(|d| d.arrow())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::{ANSI_BOLD, ANSI_DIM};
+
+    fn location(row: usize, col: usize) -> Location {
+        Location { row, col }
+    }
+
+    #[test]
+    fn test_render_plain() {
+        let map: Grid<char> = "S..\n.#.\n..E".parse().unwrap();
+        let path = [
+            location(0, 0),
+            location(0, 1),
+            location(0, 2),
+            location(1, 2),
+            location(2, 2),
+        ];
+        let rendered = Renderer::new()
+            .color('#', ANSI_DIM)
+            .path(&path, ANSI_BOLD)
+            .mark([location(2, 0)], 'O', ANSI_BOLD)
+            .plain()
+            .render(&map);
+        assert_eq!(rendered, ">>v\n.#v\nO.E\n");
+    }
+
+    #[test]
+    fn test_render_colors() {
+        let map: Grid<char> = "#.#".parse().unwrap();
+        let rendered = Renderer::new()
+            .color('#', ANSI_DIM)
+            .highlight([location(0, 2)], ANSI_BOLD)
+            .render(&map);
+        assert_eq!(
+            rendered,
+            format!("{ANSI_DIM}#{ANSI_RESET}.{ANSI_BOLD}#{ANSI_RESET}\n")
+        );
+    }
+}
@@ -0,0 +1,242 @@
+//! Hash-backed grids for large, mostly empty or unbounded maps.
+//!
+//! Only cells that have been set are stored; every other cell reads as the
+//! grid's default value. Cells are keyed by [`Point`], so they may lie at
+//! negative coordinates.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use super::{BoundingBox, Grid, GridLike, Location, Point};
+
+/// A grid that stores only the cells that have been set.
+///
+/// The bounds grow as cells are set, in any direction, so the grid has no
+/// fixed size. [`SparseGrid::with_dimensions`] makes it cover at least a
+/// board of known size from the origin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    default: T,
+    bounds: Option<(Point, Point)>,
+    min_dimensions: (usize, usize),
+}
+
+impl<T> SparseGrid<T> {
+    /// An empty, unbounded grid where every cell reads as `default`.
+    pub fn new(default: T) -> Self {
+        Self::with_dimensions(0, 0, default)
+    }
+
+    /// An empty grid that covers at least `height` x `width` from the origin,
+    /// e.g. for a board of known size that may have empty rows or columns at
+    /// its edges.
+    pub fn with_dimensions(height: usize, width: usize, default: T) -> Self {
+        Self {
+            cells: HashMap::new(),
+            default,
+            bounds: None,
+            min_dimensions: (height, width),
+        }
+    }
+
+    /// The value every unset cell reads as.
+    pub fn default_value(&self) -> &T {
+        &self.default
+    }
+
+    /// Number of stored cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The stored value at `point`, or the default for unset cells.
+    pub fn get(&self, point: Point) -> &T {
+        self.cells.get(&point).unwrap_or(&self.default)
+    }
+
+    /// Store `value` at `point`, growing the bounds to include it.
+    pub fn set(&mut self, point: Point, value: T) {
+        self.bounds = Some(match self.bounds {
+            None => (point, point),
+            Some((min, max)) => (
+                Point::new(min.x.min(point.x), min.y.min(point.y)),
+                Point::new(max.x.max(point.x), max.y.max(point.y)),
+            ),
+        });
+        self.cells.insert(point, value);
+    }
+
+    pub fn is_set(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    /// Remove a stored cell so it reads as the default again.
+    ///
+    /// The bounds are not shrunk, as that would need a scan of every cell.
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        self.cells.remove(&point)
+    }
+
+    /// The smallest and largest corners of the box holding every stored
+    /// cell, or `None` if there are none.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    /// The corners of the area shown by [`SparseGrid::to_grid`] and when
+    /// printing: the bounds, widened to cover the origin and the minimum
+    /// dimensions.
+    fn extent(&self) -> (Point, Point) {
+        let (height, width) = self.min_dimensions;
+        let corner = Point::new(width as i64 - 1, height as i64 - 1);
+        match self.bounds {
+            Some((min, max)) => (
+                Point::new(min.x.min(0), min.y.min(0)),
+                Point::new(max.x.max(corner.x), max.y.max(corner.y)),
+            ),
+            None => (Point::ORIGIN, corner),
+        }
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// Fill a dense grid covering the origin, the minimum dimensions and
+    /// every stored cell, using the default for unset cells. The top-left
+    /// corner of the result is the smallest point shown.
+    pub fn to_grid(&self) -> Grid<T> {
+        let (min, max) = self.extent();
+        let height = (max.y - min.y + 1) as usize;
+        let width = (max.x - min.x + 1) as usize;
+        Grid::from_fn(height, width, |location| {
+            self.get(min + Point::from(location)).clone()
+        })
+    }
+}
+
+impl SparseGrid<char> {
+    pub fn print(&self) {
+        print!("{self}");
+    }
+}
+
+/// Views the cells at non-negative coordinates, where a [`Point`] is also a
+/// [`Location`]. Cells at negative coordinates are left out.
+impl<T> GridLike<T> for SparseGrid<T> {
+    /// The stored value, or the default for unset cells. Never `None`.
+    fn get(&self, location: Location) -> Option<&T> {
+        Some(SparseGrid::get(self, Point::from(location)))
+    }
+
+    fn set(&mut self, location: Location, value: T) {
+        SparseGrid::set(self, Point::from(location), value);
+    }
+
+    fn dimensions(&self) -> (usize, usize) {
+        let (_, max) = self.extent();
+        ((max.y + 1) as usize, (max.x + 1) as usize)
+    }
+
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        let (min, max) = self.bounds?;
+        let top_left = Location::try_from(Point::new(min.x.max(0), min.y.max(0))).ok()?;
+        let bottom_right = Location::try_from(max).ok()?;
+        Some(BoundingBox {
+            top_left,
+            bottom_right,
+        })
+    }
+
+    fn entries<'a>(&'a self) -> impl Iterator<Item = (Location, &'a T)>
+    where
+        T: 'a,
+    {
+        let mut entries: Vec<(Location, &T)> = self
+            .cells
+            .iter()
+            .filter_map(|(&point, value)| Some((Location::try_from(point).ok()?, value)))
+            .collect();
+        entries.sort_unstable_by_key(|&(location, _)| location);
+        entries.into_iter()
+    }
+}
+
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (min, max) = self.extent();
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                write!(f, "{}", self.get(Point::new(x, y)))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparse_grid() {
+        let mut grid = SparseGrid::new('.');
+        assert_eq!(GridLike::dimensions(&grid), (0, 0));
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.bounding_box(), None);
+
+        GridLike::set(&mut grid, Location { row: 1, col: 3 }, 'R');
+        GridLike::set(&mut grid, Location { row: 2, col: 1 }, 'R');
+        assert_eq!(
+            GridLike::get(&grid, Location { row: 1, col: 3 }),
+            Some(&'R')
+        );
+        assert_eq!(grid.get(Point::new(500, -500)), &'.');
+        assert_eq!(GridLike::dimensions(&grid), (3, 4));
+        assert_eq!(
+            grid.bounding_box(),
+            Some(BoundingBox {
+                top_left: Location { row: 1, col: 1 },
+                bottom_right: Location { row: 2, col: 3 },
+            })
+        );
+        assert_eq!(grid.to_string(), "....\n...R\n.R..\n");
+        assert_eq!(
+            grid.find_all(|&c| c == 'R'),
+            [Location { row: 1, col: 3 }, Location { row: 2, col: 1 }]
+        );
+    }
+
+    #[test]
+    fn test_sparse_negative() {
+        let mut grid = SparseGrid::new('.');
+        grid.set(Point::new(-2, -1), '#');
+        grid.set(Point::new(1, 1), '#');
+        assert_eq!(grid.bounds(), Some((Point::new(-2, -1), Point::new(1, 1))));
+        assert_eq!(grid.to_string(), "#...\n....\n...#\n");
+        assert_eq!(grid.to_grid().dimensions(), (3, 4));
+        // Only the cell in the non-negative quadrant is a location.
+        assert_eq!(grid.find_all(|&c| c == '#'), [Location { row: 1, col: 1 }]);
+        assert_eq!(grid.remove(Point::new(-2, -1)), Some('#'));
+        assert!(!grid.is_set(Point::new(-2, -1)));
+    }
+
+    #[test]
+    fn test_sparse_matches_dense() {
+        let dense: Grid<char> = "#..\n..#".parse().unwrap();
+        let mut sparse = SparseGrid::with_dimensions(2, 3, '.');
+        for location in dense.find_all(|&c| c == '#') {
+            GridLike::set(&mut sparse, location, '#');
+        }
+        assert_eq!(sparse.to_grid(), dense);
+        assert_eq!(GridLike::dimensions(&sparse), GridLike::dimensions(&dense));
+        assert_eq!(
+            sparse.find_all(|&c| c == '#'),
+            dense.find_all(|&c| c == '#')
+        );
+    }
+}
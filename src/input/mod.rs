@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::grid::{Cell, Grid, GridError};
+
+pub use span::*;
+
+mod span;
+
+/// Parse a rectangular map of cells, panicking on ragged rows or unknown characters.
+///
+/// `Grid<char>` accepts any character; puzzle cell enums reject the ones they
+/// do not define.
+pub fn parse_grid<C: Cell>(input: &str) -> Grid<C> {
+    try_parse_grid(input).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Parse a rectangular map of cells, or report the first ragged row or unknown character.
+pub fn try_parse_grid<C: Cell>(input: &str) -> Result<Grid<C>, GridError> {
+    input.parse()
+}
+
+pub fn parse_2d_vector(input: &str) -> Vec<Vec<char>> {
+    parse_grid::<char>(input).into_rows()
+}
+
+/// Borrow each row of a byte grid, without copying or UTF-8 decoding.
+///
+/// Handles `\n` and `\r\n` line endings; a final line ending is optional.
+pub fn parse_2d_bytes(input: &[u8]) -> Vec<&[u8]> {
+    let input = input.strip_suffix(b"\n").unwrap_or(input);
+    if input.is_empty() {
+        return Vec::new();
+    }
+    input
+        .split(|&b| b == b'\n')
+        .map(|row| row.strip_suffix(b"\r").unwrap_or(row))
+        .collect()
+}
+
+pub fn parse_2d_digit_vector(input: &str) -> Vec<Vec<u32>> {
+    input
+        .lines()
+        .map(|line| {
+            line.trim()
+                .chars()
+                .map(|c| c.to_digit(10).expect("invalid digit character"))
+                .collect()
+        })
+        .collect()
+}
+
+/// Parse each line of input into type T using FromStr, panicking on the first bad line.
+///
+/// Skips empty lines and trims whitespace from each line.
+pub fn parse_lines<T>(input: &str) -> Vec<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    try_parse_lines(input).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Parse each non-empty, trimmed line of input into type T, or report the first bad line.
+pub fn try_parse_lines<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    Span::new(input).lines_of()
+}
+
+/// Parse each non-empty, trimmed line read from `reader` into type T, one line
+/// at a time, so the whole input never has to be in memory.
+///
+/// Errors report the line number within the stream.
+pub fn stream_lines<T>(reader: impl BufRead) -> impl Iterator<Item = Result<T, ParseError>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    reader
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            let line = line.map_err(|e| ParseError {
+                line: index + 1,
+                column: 1,
+                text: String::new(),
+                message: format!("could not read line: {e}"),
+            })?;
+            Span::starting_at(&line, index + 1, 1)
+                .lines_of::<T>()
+                .map(|values| values.into_iter().next())
+        })
+        .filter_map(Result::transpose)
+}
+
+/// Parse pairs of values separated by whitespace from each line, panicking on
+/// the first bad line.
+///
+/// Returns two vectors: all left values and all right values.
+pub fn parse_pairs<T>(input: &str) -> (Vec<T>, Vec<T>)
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    try_parse_pairs(input).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Parse pairs of values separated by whitespace from each non-empty line, or
+/// report the first bad line.
+pub fn try_parse_pairs<T>(input: &str) -> Result<(Vec<T>, Vec<T>), ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    Span::new(input)
+        .lines()
+        .filter(|line| !line.is_blank())
+        .map(|line| {
+            let mut words = line.words();
+            let left = words
+                .next()
+                .ok_or_else(|| line.error("missing left value"))?
+                .parse()?;
+            let right = words
+                .next()
+                .ok_or_else(|| line.error("missing right value"))?
+                .parse()?;
+            Ok((left, right))
+        })
+        .collect()
+}
+
+/// Every run of digits in `text`, such as `[94, 34]` from `"X+94, Y-34"`,
+/// panicking if one does not fit in a `T`.
+pub fn ints<T>(text: &str) -> Vec<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    Span::new(text).ints().unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Every integer in `text`, negative signs included, such as `[94, -34]` from
+/// `"X+94, Y-34"`, panicking if one does not fit in a `T`.
+pub fn signed_ints<T>(text: &str) -> Vec<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    Span::new(text)
+        .signed_ints()
+        .unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Split input into the groups of lines between blank lines.
+///
+/// Handles `\r\n` line endings, whitespace-only lines and runs of several
+/// blank lines, so `"a\r\n \r\n\r\nb\n"` gives `["a", "b"]`.
+pub fn sections(input: &str) -> Vec<&str> {
+    Span::new(input)
+        .sections()
+        .into_iter()
+        .map(|section| section.text().trim_end())
+        .collect()
+}
+
+/// Parse input made of exactly two [`sections`], panicking if it is not.
+pub fn sections2<A, B>(input: &str) -> (A, B)
+where
+    A: FromStr,
+    A::Err: Display,
+    B: FromStr,
+    B::Err: Display,
+{
+    try_sections2(input).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Parse input made of exactly two [`sections`], or report which part is wrong.
+pub fn try_sections2<A, B>(input: &str) -> Result<(A, B), ParseError>
+where
+    A: FromStr,
+    A::Err: Display,
+    B: FromStr,
+    B::Err: Display,
+{
+    let [a, b] = Span::new(input).sections_exact()?;
+    Ok((a.parse()?, b.parse()?))
+}
+
+/// Parse input made of exactly three [`sections`], panicking if it is not.
+pub fn sections3<A, B, C>(input: &str) -> (A, B, C)
+where
+    A: FromStr,
+    A::Err: Display,
+    B: FromStr,
+    B::Err: Display,
+    C: FromStr,
+    C::Err: Display,
+{
+    try_sections3(input).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Parse input made of exactly three [`sections`], or report which part is wrong.
+pub fn try_sections3<A, B, C>(input: &str) -> Result<(A, B, C), ParseError>
+where
+    A: FromStr,
+    A::Err: Display,
+    B: FromStr,
+    B::Err: Display,
+    C: FromStr,
+    C::Err: Display,
+{
+    let [a, b, c] = Span::new(input).sections_exact()?;
+    Ok((a.parse()?, b.parse()?, c.parse()?))
+}
+
+/// Count the frequency of each item in a slice.
+///
+/// Returns a HashMap where keys are items and values are counts.
+pub fn count_frequencies<T>(items: &[T]) -> HashMap<T, u64>
+where
+    T: Eq + Hash + Copy,
+{
+    let mut counts = HashMap::new();
+    for &item in items {
+        counts
+            .entry(item)
+            .and_modify(|count| *count += 1)
+            .or_insert(1);
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_2d_bytes() {
+        assert_eq!(parse_2d_bytes(b"#.\r\n.#\r\n"), [b"#.", b".#"]);
+        assert_eq!(parse_2d_bytes(b"ab\ncd"), [b"ab", b"cd"]);
+        assert!(parse_2d_bytes(b"").is_empty());
+    }
+
+    #[test]
+    fn test_stream_lines() {
+        let reader = std::io::Cursor::new("1\n\n2\nx\n");
+        let values: Vec<Result<u8, ParseError>> = stream_lines(reader).collect();
+        assert_eq!(values[..2], [Ok(1), Ok(2)]);
+        assert_eq!(values[2].as_ref().unwrap_err().line, 4);
+    }
+}
@@ -0,0 +1,466 @@
+//! Position-aware parsing combinators.
+//!
+//! A [`Span`] is a slice of the puzzle input that remembers where it started,
+//! so every split, trim and parse can report a [`ParseError`] pointing at the
+//! exact line and column of the bad text.
+
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Why some input could not be parsed, and where.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line of the offending text.
+    pub line: usize,
+    /// 1-based column, in characters, of the offending text.
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {} in {:?}",
+            self.line, self.column, self.message, self.text
+        )
+    }
+}
+
+/// A piece of the input text along with the position it starts at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span<'a> {
+    text: &'a str,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Span<'a> {
+    /// The whole input, starting at line 1, column 1.
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    /// Text that starts at `line` and `column` of some larger input.
+    pub fn starting_at(text: &'a str, line: usize, column: usize) -> Self {
+        Self { text, line, column }
+    }
+
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn is_blank(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    /// An error pointing at the start of this span.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            text: self.text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// The span without leading and trailing whitespace.
+    pub fn trim(self) -> Self {
+        let start = self.text.len() - self.text.trim_start().len();
+        let end = self.text.trim_end().len().max(start);
+        self.slice(start, end)
+    }
+
+    /// Every line, with line endings (`\n` or `\r\n`) removed.
+    pub fn lines(self) -> impl Iterator<Item = Span<'a>> {
+        self.line_bounds()
+            .enumerate()
+            .map(move |(index, (start, end))| self.line_span(index, start, end))
+    }
+
+    /// Groups of consecutive lines separated by one or more blank lines.
+    pub fn sections(self) -> Vec<Span<'a>> {
+        let mut result = Vec::new();
+        // (index of first line, start of first line, end of latest line)
+        let mut section: Option<(usize, usize, usize)> = None;
+        for (index, (start, end)) in self.line_bounds().enumerate() {
+            if self.text[start..end].trim().is_empty() {
+                if let Some((first, first_start, last_end)) = section.take() {
+                    result.push(self.line_span(first, first_start, last_end));
+                }
+            } else {
+                section = Some(match section {
+                    Some((first, first_start, _)) => (first, first_start, end),
+                    None => (index, start, end),
+                });
+            }
+        }
+        if let Some((first, first_start, last_end)) = section {
+            result.push(self.line_span(first, first_start, last_end));
+        }
+        result
+    }
+
+    /// Exactly `N` [`sections`](Span::sections), or an error saying how many
+    /// there were instead.
+    pub fn sections_exact<const N: usize>(self) -> Result<[Span<'a>; N], ParseError> {
+        let sections = self.sections();
+        let count = sections.len();
+        sections
+            .try_into()
+            .map_err(|_| self.error(format!("expected {N} sections, found {count}")))
+    }
+
+    /// Pieces separated by `separator`, keeping empty pieces.
+    pub fn split(self, separator: &'a str) -> impl Iterator<Item = Span<'a>> {
+        let mut offset = 0;
+        self.text.split(separator).map(move |piece| {
+            let start = offset;
+            offset += piece.len() + separator.len();
+            self.slice(start, start + piece.len())
+        })
+    }
+
+    /// Pieces separated by runs of whitespace.
+    pub fn words(self) -> impl Iterator<Item = Span<'a>> {
+        self.split(" ")
+            .flat_map(|piece| piece.split("\t"))
+            .filter(|piece| !piece.text.is_empty())
+    }
+
+    /// The text before and after the first `separator`.
+    pub fn split_once(self, separator: &str) -> Result<(Span<'a>, Span<'a>), ParseError> {
+        let start = self
+            .text
+            .find(separator)
+            .ok_or_else(|| self.error(format!("expected {separator:?}")))?;
+        Ok((
+            self.slice(0, start),
+            self.slice(start + separator.len(), self.text.len()),
+        ))
+    }
+
+    /// The rest of the span after `prefix`, which must be there.
+    pub fn strip_prefix(self, prefix: &str) -> Result<Span<'a>, ParseError> {
+        if self.text.starts_with(prefix) {
+            Ok(self.slice(prefix.len(), self.text.len()))
+        } else {
+            Err(self.error(format!("expected {prefix:?}")))
+        }
+    }
+
+    /// Parse the trimmed span as a `T`.
+    pub fn parse<T>(self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let trimmed = self.trim();
+        trimmed
+            .text
+            .parse()
+            .map_err(|e| trimmed.error(format!("invalid value: {e}")))
+    }
+
+    /// Parse every non-blank line as a `T`.
+    pub fn lines_of<T>(self) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.lines()
+            .filter(|line| !line.is_blank())
+            .map(Span::parse)
+            .collect()
+    }
+
+    /// Parse every piece between `separator`s as a `T`.
+    pub fn separated<T>(self, separator: &'a str) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.split(separator).map(Span::parse).collect()
+    }
+
+    /// Parse `key<separator>value` into its two halves.
+    pub fn key_value<K, V>(self, separator: &str) -> Result<(K, V), ParseError>
+    where
+        K: FromStr,
+        K::Err: Display,
+        V: FromStr,
+        V::Err: Display,
+    {
+        let (key, value) = self.split_once(separator)?;
+        Ok((key.parse()?, value.parse()?))
+    }
+
+    /// Every run of digits, parsed as a `T`; other characters, signs included,
+    /// only separate numbers.
+    pub fn ints<T>(self) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.numbers(false)
+    }
+
+    /// Every integer, with a `-` directly before its digits making it negative.
+    pub fn signed_ints<T>(self) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.numbers(true)
+    }
+
+    /// The pieces standing in for each `{}` in `pattern`, whose other text
+    /// must match exactly. A `{}` extends to the first match of the text
+    /// after it, or to the end of the span when it ends the pattern.
+    ///
+    /// Usually called through [`scan!`](crate::scan).
+    pub fn scan_fields(self, pattern: &str) -> Result<Vec<Span<'a>>, ParseError> {
+        let mut literals = pattern.split("{}");
+        let first = literals.next().unwrap_or_default();
+        let mut rest = self.strip_prefix(first)?;
+
+        let mut fields = Vec::new();
+        for literal in literals {
+            let (field, after) = if literal.is_empty() {
+                (rest, rest.slice(rest.text.len(), rest.text.len()))
+            } else {
+                rest.split_once(literal)?
+            };
+            if field.text.is_empty() {
+                return Err(field.error("expected a value"));
+            }
+            fields.push(field);
+            rest = after;
+        }
+
+        if rest.text.is_empty() {
+            Ok(fields)
+        } else {
+            Err(rest.error("unexpected trailing text"))
+        }
+    }
+
+    /// The sub-span covering bytes `start..end` of this span's text.
+    fn slice(self, start: usize, end: usize) -> Span<'a> {
+        let before = &self.text[..start];
+        let (line, column) = match before.rfind('\n') {
+            Some(newline) => (
+                self.line + before.matches('\n').count(),
+                before[newline + 1..].chars().count() + 1,
+            ),
+            None => (self.line, self.column + before.chars().count()),
+        };
+        Span {
+            text: &self.text[start..end],
+            line,
+            column,
+        }
+    }
+
+    /// Integers found by [`ints`](Span::ints) or [`signed_ints`](Span::signed_ints).
+    fn numbers<T>(self, signed: bool) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let bytes = self.text.as_bytes();
+        let mut result = Vec::new();
+        let mut index = 0;
+        while index < bytes.len() {
+            if !bytes[index].is_ascii_digit() {
+                index += 1;
+                continue;
+            }
+            let mut start = index;
+            if signed && start > 0 && bytes[start - 1] == b'-' {
+                start -= 1;
+            }
+            while index < bytes.len() && bytes[index].is_ascii_digit() {
+                index += 1;
+            }
+            let number = self.text[start..index].parse().map_err(|e| {
+                self.slice(start, index)
+                    .error(format!("invalid value: {e}"))
+            })?;
+            result.push(number);
+        }
+        Ok(result)
+    }
+
+    /// The sub-span covering bytes `start..end`, which begin the `index`th line.
+    fn line_span(self, index: usize, start: usize, end: usize) -> Span<'a> {
+        Span {
+            text: &self.text[start..end],
+            line: self.line + index,
+            column: if index == 0 { self.column } else { 1 },
+        }
+    }
+
+    /// Byte ranges of each line's content, without its line ending.
+    fn line_bounds(self) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let mut offset = 0;
+        self.text.split_inclusive('\n').map(move |line| {
+            let start = offset;
+            offset += line.len();
+            let content = line.trim_end_matches('\n').trim_end_matches('\r');
+            (start, start + content.len())
+        })
+    }
+}
+
+impl<'a> From<&'a str> for Span<'a> {
+    fn from(text: &'a str) -> Self {
+        Span::new(text)
+    }
+}
+
+/// Match text against a pattern with a `{}` for each value and parse the
+/// values as the listed types, giving a `Result` of a tuple.
+///
+/// The text can be a `&str` or a [`Span`], whose position is kept in errors.
+///
+/// ```
+/// let robot = advent_of_code::scan!("p=0,4 v=3,-3", "p={},{} v={},{}", usize, usize, i32, i32);
+/// assert_eq!(robot, Ok((0, 4, 3, -3)));
+/// ```
+#[macro_export]
+macro_rules! scan {
+    ($text:expr, $pattern:literal, $($ty:ty),+ $(,)?) => {
+        $crate::input::Span::from($text)
+            .scan_fields($pattern)
+            .and_then(|fields| -> Result<_, $crate::input::ParseError> {
+                let mut fields = fields.into_iter();
+                Ok(($(
+                    fields
+                        .next()
+                        .expect("scan! needs one type for each {} in the pattern")
+                        .parse::<$ty>()?,
+                )+))
+            })
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_span_positions() {
+        let input = "a: 1\r\nb: x\n\n\n  c: 3\n";
+        let sections = Span::new(input).sections();
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].text(), "a: 1\r\nb: x");
+        assert_eq!((sections[1].line(), sections[1].column()), (5, 1));
+
+        let pairs: Result<Vec<(String, u32)>, ParseError> = sections[0]
+            .lines()
+            .map(|line| line.key_value(": "))
+            .collect();
+        assert_eq!(
+            pairs,
+            Err(ParseError {
+                line: 2,
+                column: 4,
+                text: "x".to_string(),
+                message: "invalid value: invalid digit found in string".to_string(),
+            })
+        );
+
+        let trimmed = sections[1].trim();
+        assert_eq!((trimmed.line(), trimmed.column()), (5, 3));
+        assert_eq!(trimmed.key_value(": "), Ok(("c".to_string(), 3)));
+    }
+
+    #[test]
+    fn test_span_combinators() {
+        assert_eq!(Span::new("1\n2\n\n3\n").lines_of::<u8>(), Ok(vec![1, 2, 3]));
+        assert_eq!(Span::new("4, 5,6").separated::<u8>(","), Ok(vec![4, 5, 6]));
+
+        let error = Span::new("4,5,x6").separated::<u8>(",").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (1, 5, "x6")
+        );
+
+        let register = Span::new("Register A: 729").strip_prefix("Register A: ");
+        assert_eq!(register.and_then(Span::parse::<u64>), Ok(729));
+        assert!(Span::new("Register B: 0")
+            .strip_prefix("Register A: ")
+            .is_err());
+
+        let words: Vec<&str> = Span::new("x00  AND\ty01")
+            .words()
+            .map(|w| w.text())
+            .collect();
+        assert_eq!(words, ["x00", "AND", "y01"]);
+        assert_eq!(
+            Span::new("no separator")
+                .split_once(" -> ")
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 1: expected \" -> \" in \"no separator\""
+        );
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = "#.\r\n.#  \r\n \r\n\r\n\r\n<>^v\r\n";
+        assert_eq!(crate::input::sections(input), ["#.\r\n.#", "<>^v"]);
+
+        let (grid, steps): (crate::grid::Grid<char>, String) = crate::input::sections2(input);
+        assert_eq!(grid.to_string(), "#.\n.#\n");
+        assert_eq!(steps, "<>^v");
+
+        let error = crate::input::try_sections3::<String, String, String>(input).unwrap_err();
+        assert_eq!(error.message, "expected 3 sections, found 2");
+    }
+
+    #[test]
+    fn test_ints() {
+        let line = Span::new("Button A: X+94, Y-34 at 1-3");
+        assert_eq!(line.ints::<u32>(), Ok(vec![94, 34, 1, 3]));
+        assert_eq!(line.signed_ints::<i32>(), Ok(vec![94, -34, 1, -3]));
+
+        let error = Span::new("a\nb 300").ints::<u8>().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    #[test]
+    fn test_scan() {
+        let button = crate::scan!("Button B: X+22, Y+67", "Button B: X+{}, Y+{}", u64, u64);
+        assert_eq!(button, Ok((22, 67)));
+
+        let line = Span::new("x\nmove 3 from 1 to 2").lines().nth(1).unwrap();
+        let step = crate::scan!(line, "move {} from {} to {}", u8, u8, char);
+        assert_eq!(step, Ok((3, 1, '2')));
+
+        let error = crate::scan!(line, "move {} from {} to {}", u8, bool, u8).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (2, 13, "1")
+        );
+        assert!(crate::scan!("Prize: X=1", "Prize: X={}, Y={}", u64, u64).is_err());
+        assert!(crate::scan!("p=1 v=2!", "p={} v={}", u8, u8).is_err());
+    }
+}
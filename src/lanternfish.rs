@@ -1,10 +1,25 @@
 use std::collections::VecDeque;
+use std::io;
+use std::path::Path;
 
-use crate::grid;
+use crate::grid::{self, Cell};
+use crate::viz;
+
+crate::cell_enum! {
+    /// A square of the warehouse floor plan.
+    pub enum Tile {
+        Wall = '#',
+        Floor = '.',
+        Box = 'O',
+        BoxLeft = '[',
+        BoxRight = ']',
+        Robot = '@',
+    }
+}
 
 #[derive(Debug)]
 pub struct Lanternfish {
-    warehouse: Vec<Vec<char>>,
+    warehouse: grid::Grid<Tile>,
     layout: Layout,
     robot_position: grid::Location,
 }
@@ -15,9 +30,9 @@ enum Layout {
     Wide,
 }
 
-impl From<Vec<Vec<char>>> for Lanternfish {
-    fn from(value: Vec<Vec<char>>) -> Self {
-        let robot_position = grid::find_only(&value, '@');
+impl From<grid::Grid<Tile>> for Lanternfish {
+    fn from(value: grid::Grid<Tile>) -> Self {
+        let robot_position = value.find_only(Tile::Robot);
         let layout = Lanternfish::layout(&value);
 
         Self {
@@ -36,27 +51,51 @@ impl Lanternfish {
         }
     }
 
+    pub fn move_robots(&mut self, directions: &[grid::Direction]) {
+        self.move_robots_observed(directions, &mut ());
+    }
+
+    /// Like [`Lanternfish::move_robots`], but shows the warehouse to `observer`
+    /// before the first move and after every move.
+    pub fn move_robots_observed(
+        &mut self,
+        directions: &[grid::Direction],
+        observer: &mut impl viz::Observer,
+    ) {
+        observer.observe_with(|| self.warehouse.to_chars());
+        for &d in directions {
+            self.move_robot(d);
+            observer.observe_with(|| self.warehouse.to_chars());
+        }
+    }
+
     pub fn gps_sum(&self) -> u64 {
         self.warehouse
-            .iter()
-            .enumerate()
-            .map(|(row, row_val)| {
-                row_val
-                    .iter()
-                    .enumerate()
-                    .map(|(col, _)| self.gps_coordinate(grid::Location { row, col }))
-                    .sum::<u64>()
-            })
+            .locations()
+            .map(|location| self.gps_coordinate(location))
             .sum()
     }
 
-    fn write_robot_move(&mut self, direction: grid::Direction) -> (grid::Location, char) {
-        self.warehouse[self.robot_position.row][self.robot_position.col] = '.';
-        let next = grid::get_location(&self.warehouse, self.robot_position, direction)
+    /// Save the warehouse as an SVG, with boxes in brown and the robot in red.
+    pub fn write_snapshot(&self, path: &Path) -> io::Result<()> {
+        let boxes = viz::Rgb(181, 121, 66);
+        let palette = viz::Palette::default()
+            .with(Tile::Box.to_char(), boxes)
+            .with(Tile::BoxLeft.to_char(), boxes)
+            .with(Tile::BoxRight.to_char(), boxes)
+            .with(Tile::Robot.to_char(), viz::Rgb(220, 40, 40));
+        viz::write_svg(path, &self.warehouse.to_chars(), &palette, 10)
+    }
+
+    fn write_robot_move(&mut self, direction: grid::Direction) -> (grid::Location, Tile) {
+        self.warehouse[self.robot_position] = Tile::Floor;
+        let next = self
+            .warehouse
+            .get_location(self.robot_position, direction)
             .expect("robot move should be in bounds");
         self.robot_position = next;
-        let overwritten = grid::at(&self.warehouse, next);
-        self.warehouse[self.robot_position.row][self.robot_position.col] = '@';
+        let overwritten = self.warehouse[next];
+        self.warehouse[self.robot_position] = Tile::Robot;
         (next, overwritten)
     }
 
@@ -66,20 +105,24 @@ impl Lanternfish {
             .expect("should have empty space for box move");
         let mut first_box_half = start;
         let (first_box, second_box) = if direction == grid::Direction::Left {
-            (']', '[')
+            (Tile::BoxRight, Tile::BoxLeft)
         } else {
-            ('[', ']')
+            (Tile::BoxLeft, Tile::BoxRight)
         };
 
         let num_boxes = empty_location.col.abs_diff(first_box_half.col).div_ceil(2);
         for _ in 0..num_boxes {
-            let second_box_half = grid::get_location(&self.warehouse, first_box_half, direction)
+            let second_box_half = self
+                .warehouse
+                .get_location(first_box_half, direction)
                 .expect("box half should be in bounds");
 
-            self.warehouse[first_box_half.row][first_box_half.col] = first_box;
-            self.warehouse[second_box_half.row][second_box_half.col] = second_box;
+            self.warehouse[first_box_half] = first_box;
+            self.warehouse[second_box_half] = second_box;
 
-            first_box_half = grid::get_location(&self.warehouse, second_box_half, direction)
+            first_box_half = self
+                .warehouse
+                .get_location(second_box_half, direction)
                 .expect("next box half should be in bounds");
         }
     }
@@ -90,36 +133,42 @@ impl Lanternfish {
         mut boxes: VecDeque<grid::Location>,
     ) {
         while let Some(left_box_half) = boxes.pop_front() {
-            let new_left = grid::get_location(&self.warehouse, left_box_half, direction)
+            let new_left = self
+                .warehouse
+                .get_location(left_box_half, direction)
                 .expect("vertical box move should be in bounds");
-            let new_right = grid::get_location(&self.warehouse, new_left, grid::Direction::Right)
+            let new_right = self
+                .warehouse
+                .get_location(new_left, grid::Direction::Right)
                 .expect("right box half should be in bounds");
 
-            let overwritten_left = grid::at(&self.warehouse, new_left);
-            let overwritten_right = grid::at(&self.warehouse, new_right);
+            let overwritten_left = self.warehouse[new_left];
+            let overwritten_right = self.warehouse[new_right];
 
-            if overwritten_left == '[' {
+            if overwritten_left == Tile::BoxLeft {
                 boxes.push_back(new_left);
                 continue;
-            } else if overwritten_left == ']' {
-                let displaced_left =
-                    grid::get_location(&self.warehouse, new_left, grid::Direction::Left)
-                        .expect("displaced left should be in bounds");
-                self.warehouse[displaced_left.row][displaced_left.col] = '.';
-                self.warehouse[new_left.row][new_left.col] = '[';
+            } else if overwritten_left == Tile::BoxRight {
+                let displaced_left = self
+                    .warehouse
+                    .get_location(new_left, grid::Direction::Left)
+                    .expect("displaced left should be in bounds");
+                self.warehouse[displaced_left] = Tile::Floor;
+                self.warehouse[new_left] = Tile::BoxLeft;
                 boxes.push_back(displaced_left);
-            } else if overwritten_left == '.' {
-                self.warehouse[new_left.row][new_left.col] = '[';
+            } else if overwritten_left == Tile::Floor {
+                self.warehouse[new_left] = Tile::BoxLeft;
             }
-            if overwritten_right == '[' {
-                let displaced_right =
-                    grid::get_location(&self.warehouse, new_right, grid::Direction::Right)
-                        .expect("displaced right should be in bounds");
-                self.warehouse[displaced_right.row][displaced_right.col] = '.';
-                self.warehouse[new_right.row][new_right.col] = ']';
+            if overwritten_right == Tile::BoxLeft {
+                let displaced_right = self
+                    .warehouse
+                    .get_location(new_right, grid::Direction::Right)
+                    .expect("displaced right should be in bounds");
+                self.warehouse[displaced_right] = Tile::Floor;
+                self.warehouse[new_right] = Tile::BoxRight;
                 boxes.push_back(new_right);
-            } else if overwritten_right == '.' {
-                self.warehouse[new_right.row][new_right.col] = ']';
+            } else if overwritten_right == Tile::Floor {
+                self.warehouse[new_right] = Tile::BoxRight;
             }
         }
     }
@@ -130,30 +179,34 @@ impl Lanternfish {
         }
 
         let (next, overwritten) = self.write_robot_move(direction);
-        if overwritten == '.' {
+        if overwritten == Tile::Floor {
             return;
         }
 
         if grid::is_horizontal(direction) {
-            let start = grid::get_location(&self.warehouse, next, direction)
+            let start = self
+                .warehouse
+                .get_location(next, direction)
                 .expect("horizontal move should be in bounds");
             self.write_horizontal_box_move(start, direction);
             return;
         }
 
-        let left_box_half = if overwritten == '[' {
+        let left_box_half = if overwritten == Tile::BoxLeft {
             next
         } else {
-            grid::get_location(&self.warehouse, next, grid::Direction::Left)
+            self.warehouse
+                .get_location(next, grid::Direction::Left)
                 .expect("left box half should be in bounds")
         };
-        let empty_space = if overwritten == '[' {
-            grid::get_location(&self.warehouse, next, grid::Direction::Right)
+        let empty_space = if overwritten == Tile::BoxLeft {
+            self.warehouse
+                .get_location(next, grid::Direction::Right)
                 .expect("right side should be in bounds")
         } else {
             left_box_half
         };
-        self.warehouse[empty_space.row][empty_space.col] = '.';
+        self.warehouse[empty_space] = Tile::Floor;
         let boxes: VecDeque<grid::Location> = VecDeque::from([left_box_half]);
         self.write_vertical_box_moves(direction, boxes);
     }
@@ -166,15 +219,15 @@ impl Lanternfish {
         let (_, overwritten) = self.write_robot_move(direction);
 
         match overwritten {
-            'O' => {
-                self.warehouse[empty_space.row][empty_space.col] = 'O';
+            Tile::Box => {
+                self.warehouse[empty_space] = Tile::Box;
             }
-            '.' => {}
-            '#' => {
+            Tile::Floor => {}
+            Tile::Wall => {
                 panic!("Hit a wall when moving robot");
             }
-            c => {
-                panic!("Unexpected map character '{c}' found");
+            tile => {
+                panic!("Unexpected tile '{tile}' found");
             }
         }
     }
@@ -186,24 +239,27 @@ impl Lanternfish {
             return true;
         }
 
-        let next = grid::get_location(&self.warehouse, location, direction)
+        let next = self
+            .warehouse
+            .get_location(location, direction)
             .expect("can_move check should be in bounds");
-        let c = grid::at(&self.warehouse, next);
-
-        match c {
-            '.' => true,
-            '[' => {
-                let right_wall =
-                    grid::get_location(&self.warehouse, next, grid::Direction::Right)
-                        .expect("right side of box should be in bounds");
+        match self.warehouse[next] {
+            Tile::Floor => true,
+            Tile::BoxLeft => {
+                let right_wall = self
+                    .warehouse
+                    .get_location(next, grid::Direction::Right)
+                    .expect("right side of box should be in bounds");
                 self.can_move(right_wall, direction) && self.can_move(next, direction)
             }
-            ']' => {
-                let left_wall = grid::get_location(&self.warehouse, next, grid::Direction::Left)
+            Tile::BoxRight => {
+                let left_wall = self
+                    .warehouse
+                    .get_location(next, grid::Direction::Left)
                     .expect("left side of box should be in bounds");
                 self.can_move(left_wall, direction) && self.can_move(next, direction)
             }
-            e => panic!("Unexpected map character '{e}' found"),
+            tile => panic!("Unexpected tile '{tile}' found"),
         }
     }
 
@@ -214,42 +270,41 @@ impl Lanternfish {
     ) -> Option<grid::Location> {
         let mut position = location;
         loop {
-            let next = grid::get_location(&self.warehouse, position, direction)
+            let next = self
+                .warehouse
+                .get_location(position, direction)
                 .expect("search for empty space should stay in bounds");
 
-            match grid::at(&self.warehouse, next) {
-                '.' => {
+            match self.warehouse[next] {
+                Tile::Floor => {
                     return Some(next);
                 }
-                '#' => {
+                Tile::Wall => {
                     return None;
                 }
-                'O' | '[' | ']' => {
+                Tile::Box | Tile::BoxLeft | Tile::BoxRight => {
                     position = next;
                 }
-                c => {
-                    panic!("Unexpected map character '{}' found", c);
+                tile => {
+                    panic!("Unexpected tile '{tile}' found");
                 }
             }
         }
     }
 
     fn gps_coordinate(&self, location: grid::Location) -> u64 {
-        let c = grid::at(&self.warehouse, location);
-        match c {
-            'O' | '[' => (location.row * 100 + location.col) as u64,
+        match self.warehouse[location] {
+            Tile::Box | Tile::BoxLeft => (location.row * 100 + location.col) as u64,
             _ => 0,
         }
     }
 
-    fn layout(warehouse: &[Vec<char>]) -> Layout {
-        for row in warehouse.iter() {
-            for &col in row.iter() {
-                match col {
-                    'O' => return Layout::Standard,
-                    '[' => return Layout::Wide,
-                    _ => {}
-                }
+    fn layout(warehouse: &grid::Grid<Tile>) -> Layout {
+        for (_, &tile) in warehouse.cells() {
+            match tile {
+                Tile::Box => return Layout::Standard,
+                Tile::BoxLeft => return Layout::Wide,
+                _ => {}
             }
         }
         panic!("No boxes found in warehouse");
@@ -4,6 +4,7 @@ pub mod bfs;
 pub mod bytedodge;
 pub mod cheat;
 pub mod claw;
+pub mod dfs;
 pub mod disk;
 pub mod equation;
 pub mod garden;
@@ -25,4 +26,5 @@ pub mod stone;
 pub mod template;
 pub mod topograph;
 pub mod towel;
+pub mod viz;
 pub mod word_search;
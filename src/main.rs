@@ -7,34 +7,41 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{Day, Year};
     use std::process;
 
     pub enum AppArguments {
         Download {
             day: Day,
+            year: Option<Year>,
         },
         Read {
             day: Day,
+            year: Option<Year>,
         },
         Scaffold {
             day: Day,
             download: bool,
             overwrite: bool,
+            year: Option<Year>,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            input: Option<String>,
+            year: Option<Year>,
         },
         All {
             release: bool,
+            year: Option<Year>,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            year: Option<Year>,
         },
         #[cfg(feature = "today")]
         Today,
@@ -46,33 +53,41 @@ mod args {
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                year: args.opt_value_from_str("--year")?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let year = args.opt_value_from_str("--year")?;
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    year,
                 }
             }
             Some("download") => AppArguments::Download {
+                year: args.opt_value_from_str("--year")?,
                 day: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
+                year: args.opt_value_from_str("--year")?,
                 day: args.free_from_str()?,
             },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                year: args.opt_value_from_str("--year")?,
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                input: args.opt_value_from_str("--input")?,
+                year: args.opt_value_from_str("--year")?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -102,18 +117,24 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::All { release, year } => all::handle(release, year),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                year,
+            } => time::handle(day, all, store, year),
+            AppArguments::Download { day, year } => download::handle(day, year),
+            AppArguments::Read { day, year } => read::handle(day, year),
             AppArguments::Scaffold {
                 day,
                 download,
                 overwrite,
+                year,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(day, overwrite, year);
                 if download {
-                    download::handle(day);
+                    download::handle(day, year);
                 }
             }
             AppArguments::Solve {
@@ -121,14 +142,16 @@ fn main() {
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                input,
+                year,
+            } => solve::handle(day, release, dhat, submit, input, year),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                        scaffold::handle(day, false, None);
+                        download::handle(day, None);
+                        read::handle(day, None)
                     }
                     None => {
                         eprintln!(
@@ -3,13 +3,14 @@
 //! Finds shortest paths through a maze where turning costs 1000
 //! and moving forward costs 1.
 
-use crate::grid::{self, Direction, Location};
+use crate::grid::{self, Direction, Grid, Location, Renderer};
 use crate::pathfinding;
+use crate::template::{ANSI_BOLD, ANSI_DIM, ANSI_GREEN};
 
 /// A maze with start (S) and end (E) positions.
 #[derive(Debug)]
 pub struct Maze {
-    map: Vec<Vec<char>>,
+    map: Grid<char>,
     start: Location,
     end: Location,
 }
@@ -21,10 +22,10 @@ struct Node {
     direction: Direction,
 }
 
-impl From<Vec<Vec<char>>> for Maze {
-    fn from(value: Vec<Vec<char>>) -> Self {
-        let start = grid::find_only(&value, 'S');
-        let end = grid::find_only(&value, 'E');
+impl From<Grid<char>> for Maze {
+    fn from(value: Grid<char>) -> Self {
+        let start = value.find_only('S');
+        let end = value.find_only('E');
         Self {
             map: value,
             start,
@@ -41,30 +42,44 @@ impl Maze {
             direction: Direction::Right,
         };
 
-        pathfinding::dijkstra(
+        let heuristic = pathfinding::manhattan_with_turns(self.end, 1000);
+        pathfinding::astar(
             start,
             |node| self.get_neighbors(node),
+            |node| heuristic(&(node.location, node.direction)),
             |node| node.location == self.end,
         )
         .expect("maze should have a solution")
+        .0
     }
 
     /// Count all tiles that lie on any shortest path through the maze.
     pub fn shortest_path_tiles(&self) -> u64 {
+        self.all_shortest_paths().path_locations.len() as u64
+    }
+
+    /// Print the maze with every tile on a shortest path marked `O`.
+    pub fn print_best_paths(&self) {
+        let tiles = self.all_shortest_paths().path_locations;
+        Renderer::new()
+            .color('#', ANSI_DIM)
+            .mark(tiles, 'O', &format!("{ANSI_BOLD}{ANSI_GREEN}"))
+            .print(&self.map);
+    }
+
+    fn all_shortest_paths(&self) -> pathfinding::AllPathsResult<Location> {
         let start = Node {
             location: self.start,
             direction: Direction::Right,
         };
 
-        let result = pathfinding::dijkstra_all_paths(
+        pathfinding::dijkstra_all_paths(
             start,
             |node| self.get_neighbors(node),
             |node| node.location == self.end,
             |node| node.location,
         )
-        .expect("maze should have a solution");
-
-        result.path_locations.len() as u64
+        .expect("maze should have a solution")
     }
 
     /// Get all neighbors of a node with their movement costs.
@@ -88,8 +103,8 @@ impl Maze {
         ];
 
         // Moving forward costs 1 (if not blocked)
-        if let Some(next_loc) = grid::get_location(&self.map, node.location, node.direction) {
-            if grid::at(&self.map, next_loc) != '#' {
+        if let Some(next_loc) = self.map.get_location(node.location, node.direction) {
+            if self.map[next_loc] != '#' {
                 neighbors.push((
                     Node {
                         location: next_loc,
@@ -1,65 +1,12 @@
 //! Optimized pathfinder for Day 6.
 //!
-//! Uses bitmask arrays and parallel processing for efficient loop detection.
+//! Uses per-direction bit grids and parallel processing for efficient loop detection.
 
-use crate::grid::{Direction, Location};
+use crate::grid::{BitGrid, Direction, Grid, Location, Renderer};
+use crate::template::{ANSI_DIM, ANSI_YELLOW};
+use crate::viz::Observer;
 use rayon::prelude::*;
 
-/// Direction bitmasks for visited tracking.
-const UP: u8 = 1;
-const DOWN: u8 = 2;
-const LEFT: u8 = 4;
-const RIGHT: u8 = 8;
-
-/// Fast visited state using a flat bitmask array.
-#[derive(Clone)]
-struct VisitedGrid {
-    data: Vec<u8>,
-    width: usize,
-}
-
-impl VisitedGrid {
-    fn new(height: usize, width: usize) -> Self {
-        Self {
-            data: vec![0; height * width],
-            width,
-        }
-    }
-
-    #[inline]
-    fn index(&self, loc: Location) -> usize {
-        loc.row * self.width + loc.col
-    }
-
-    #[inline]
-    fn mark(&mut self, loc: Location, dir: Direction) {
-        let idx = self.index(loc);
-        self.data[idx] |= dir_to_bit(dir);
-    }
-
-    #[inline]
-    fn has_visited(&self, loc: Location, dir: Direction) -> bool {
-        let idx = self.index(loc);
-        (self.data[idx] & dir_to_bit(dir)) != 0
-    }
-
-    #[inline]
-    fn has_any(&self, loc: Location) -> bool {
-        let idx = self.index(loc);
-        self.data[idx] != 0
-    }
-}
-
-#[inline]
-fn dir_to_bit(dir: Direction) -> u8 {
-    match dir {
-        Direction::Up => UP,
-        Direction::Down => DOWN,
-        Direction::Left => LEFT,
-        Direction::Right => RIGHT,
-    }
-}
-
 #[inline]
 fn rotate_cw(dir: Direction) -> Direction {
     match dir {
@@ -75,7 +22,7 @@ struct ObstacleCandidate {
     obstacle_pos: Location,
     guard_pos: Location,
     guard_dir: Direction,
-    visited_snapshot: VisitedGrid,
+    visited_snapshot: BitGrid,
 }
 
 #[derive(Debug)]
@@ -104,11 +51,42 @@ impl<'a> From<&'a Vec<Vec<char>>> for Pathfinder<'a> {
 
 impl Pathfinder<'_> {
     pub fn distinct_positions(&self) -> u64 {
-        let mut visited = VisitedGrid::new(self.height, self.width);
+        let mut visited = BitGrid::new(self.height, self.width);
+        for (location, _) in self.walk() {
+            visited.insert(location);
+        }
+
+        visited.count_ones() as u64
+    }
+
+    /// The guard's walk from the start until they leave the map, one entry per step.
+    pub fn guard_path(&self) -> Vec<Location> {
+        let mut path: Vec<Location> = self
+            .walk()
+            .into_iter()
+            .map(|(location, _)| location)
+            .collect();
+        path.dedup();
+        path
+    }
+
+    /// Walk the guard off the map, returning their location and direction at
+    /// the start and after every move or turn.
+    pub fn walk(&self) -> Vec<(Location, Direction)> {
+        self.walk_observed(&mut ())
+    }
+
+    /// Like [`Pathfinder::walk`], but shows the map to `observer` before the
+    /// first step and after every step, with visited cells marked `X` and the
+    /// guard drawn as an arrow facing their direction.
+    pub fn walk_observed(&self, observer: &mut impl Observer) -> Vec<(Location, Direction)> {
+        let mut visited = BitGrid::new(self.height, self.width);
         let mut location = self.start_location;
         let mut direction = self.start_direction;
+        let mut states = vec![(location, direction)];
 
-        visited.mark(location, direction);
+        visited.insert_direction(location, direction);
+        observer.observe_with(|| self.draw_walk(&states));
 
         while !self.is_at_edge(location, direction) {
             let next = self.get_next(location, direction);
@@ -119,14 +97,39 @@ impl Pathfinder<'_> {
                 location = next;
             }
 
-            if visited.has_visited(location, direction) {
+            if visited.has_direction(location, direction) {
                 panic!("Unexpected looping path found");
             }
-            visited.mark(location, direction);
+            visited.insert_direction(location, direction);
+            states.push((location, direction));
+            observer.observe_with(|| self.draw_walk(&states));
+        }
+
+        states
+    }
+
+    /// The map with every cell of `states` marked `X`, except the last,
+    /// where the guard is drawn facing their direction.
+    fn draw_walk(&self, states: &[(Location, Direction)]) -> Grid<char> {
+        let mut map =
+            Grid::from_rows(self.map.to_vec()).expect("map rows should all be the same width");
+        for &(location, _) in states {
+            map[location] = 'X';
         }
+        if let Some(&(location, direction)) = states.last() {
+            map[location] = direction.arrow();
+        }
+        map
+    }
 
-        // Count cells with any visit
-        visited.data.iter().filter(|&&v| v != 0).count() as u64
+    /// Print the map with the guard's walk drawn as arrows.
+    pub fn print_path(&self) {
+        let map =
+            Grid::from_rows(self.map.to_vec()).expect("map rows should all be the same width");
+        Renderer::new()
+            .color('#', ANSI_DIM)
+            .path(&self.guard_path(), ANSI_YELLOW)
+            .print(&map);
     }
 
     pub fn distinct_obstacles(&self) -> u64 {
@@ -144,11 +147,11 @@ impl Pathfinder<'_> {
     /// obstacle might create a loop.
     fn collect_candidates(&self) -> Vec<ObstacleCandidate> {
         let mut candidates = Vec::new();
-        let mut visited = VisitedGrid::new(self.height, self.width);
+        let mut visited = BitGrid::new(self.height, self.width);
         let mut location = self.start_location;
         let mut direction = self.start_direction;
 
-        visited.mark(location, direction);
+        visited.insert_direction(location, direction);
 
         while !self.is_at_edge(location, direction) {
             let next = self.get_next(location, direction);
@@ -157,7 +160,7 @@ impl Pathfinder<'_> {
                 direction = rotate_cw(direction);
             } else {
                 // Check if placing obstacle at `next` is a valid candidate
-                if !visited.has_any(next) {
+                if !visited.is_set(next) {
                     candidates.push(ObstacleCandidate {
                         obstacle_pos: next,
                         guard_pos: location,
@@ -168,10 +171,10 @@ impl Pathfinder<'_> {
                 location = next;
             }
 
-            if visited.has_visited(location, direction) {
+            if visited.has_direction(location, direction) {
                 panic!("Unexpected looping path found");
             }
-            visited.mark(location, direction);
+            visited.insert_direction(location, direction);
         }
 
         candidates
@@ -186,10 +189,10 @@ impl Pathfinder<'_> {
         let mut direction = rotate_cw(candidate.guard_dir);
 
         // Mark the turn
-        if visited.has_visited(location, direction) {
+        if visited.has_direction(location, direction) {
             return true; // Already a loop from turning
         }
-        visited.mark(location, direction);
+        visited.insert_direction(location, direction);
 
         while !self.is_at_edge(location, direction) {
             let next = self.get_next(location, direction);
@@ -200,10 +203,10 @@ impl Pathfinder<'_> {
                 location = next;
             }
 
-            if visited.has_visited(location, direction) {
+            if visited.has_direction(location, direction) {
                 return true; // Found a loop!
             }
-            visited.mark(location, direction);
+            visited.insert_direction(location, direction);
         }
 
         false // Exited the grid, no loop
@@ -1,12 +1,16 @@
 //! Generic pathfinding algorithms for grid-based problems.
 //!
 //! This module provides both trait-based and closure-based Dijkstra
-//! implementations for various pathfinding scenarios.
+//! implementations for various pathfinding scenarios, plus A* with a few
+//! ready-made grid heuristics.
 
 use std::collections::hash_map::Entry;
 use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::hash::Hash;
 
+use crate::bfs::Path;
+use crate::grid::{Direction, Location, Point};
+
 /// Weighted node for priority queue ordering.
 #[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
 pub struct WeightedNode<N> {
@@ -221,6 +225,108 @@ where
     })
 }
 
+/// Find the cheapest path from start to a goal using A* search.
+///
+/// # Arguments
+/// * `start` - The starting node
+/// * `neighbors` - Function returning neighboring nodes and their costs
+/// * `heuristic` - Lower bound on the remaining cost from a node to a goal
+/// * `is_goal` - Predicate to check if a node is a goal
+///
+/// The heuristic must never overestimate (see [`manhattan`] and
+/// [`manhattan_with_turns`]); `|_| 0` makes this plain Dijkstra.
+///
+/// Returns the cost together with the path taken, or `None` if no path exists.
+pub fn astar<N, FN, FH, FG>(
+    start: N,
+    neighbors: FN,
+    heuristic: FH,
+    is_goal: FG,
+) -> Option<(u64, Path<N>)>
+where
+    N: Copy + Eq + Hash,
+    FN: Fn(&N) -> Vec<(N, u64)>,
+    FH: Fn(&N) -> u64,
+    FG: Fn(&N) -> bool,
+{
+    // Best known cost to reach each node, and the node it was reached from.
+    let mut best: HashMap<N, (u64, Option<N>)> = HashMap::from([(start, (0, None))]);
+    let mut queue: BinaryHeap<WeightedNode<N>> = BinaryHeap::from([WeightedNode {
+        weight: heuristic(&start),
+        node: start,
+    }]);
+
+    while let Some(WeightedNode { weight, node }) = queue.pop() {
+        let cost = best[&node].0;
+        if weight != cost + heuristic(&node) {
+            continue; // outdated entry, the node was reached more cheaply since
+        }
+        if is_goal(&node) {
+            let mut nodes = vec![node];
+            while let Some(parent) = best[nodes.last().unwrap()].1 {
+                nodes.push(parent);
+            }
+            nodes.reverse();
+            return Some((cost, Path::new(nodes)));
+        }
+
+        for (neighbor, step) in neighbors(&node) {
+            let new_cost = cost + step;
+            match best.entry(neighbor) {
+                Entry::Occupied(e) if e.get().0 <= new_cost => continue,
+                Entry::Occupied(mut e) => {
+                    e.insert((new_cost, Some(node)));
+                }
+                Entry::Vacant(e) => {
+                    e.insert((new_cost, Some(node)));
+                }
+            }
+            queue.push(WeightedNode {
+                weight: new_cost + heuristic(&neighbor),
+                node: neighbor,
+            });
+        }
+    }
+
+    None
+}
+
+/// Heuristic for unit-cost moves between orthogonal neighbours: the
+/// Manhattan distance to `goal`.
+pub fn manhattan(goal: Location) -> impl Fn(&Location) -> u64 {
+    move |&location| Point::from(location).manhattan(Point::from(goal))
+}
+
+/// Heuristic for `(location, facing)` nodes where stepping forward costs 1
+/// and each quarter turn costs `turn_cost`: the Manhattan distance to `goal`
+/// plus the fewest turns needed to head there.
+///
+/// ```
+/// # use advent_of_code::grid::{Direction, Location};
+/// # use advent_of_code::pathfinding::manhattan_with_turns;
+/// let heuristic = manhattan_with_turns(Location { row: 0, col: 3 }, 1000);
+/// assert_eq!(heuristic(&(Location { row: 0, col: 0 }, Direction::Right)), 3);
+/// assert_eq!(heuristic(&(Location { row: 2, col: 0 }, Direction::Right)), 1005);
+/// assert_eq!(heuristic(&(Location { row: 0, col: 5 }, Direction::Right)), 2002);
+/// ```
+pub fn manhattan_with_turns(
+    goal: Location,
+    turn_cost: u64,
+) -> impl Fn(&(Location, Direction)) -> u64 {
+    move |&(location, facing)| {
+        let offset = Point::from(goal) - Point::from(location);
+        let (dy, dx) = facing.delta();
+        let ahead = offset.y * dy as i64 + offset.x * dx as i64;
+        let aside = offset.y * dx as i64 - offset.x * dy as i64;
+        let turns = match (ahead, aside) {
+            (_, 0) if ahead >= 0 => 0,
+            _ if ahead >= 0 => 1,
+            _ => 2,
+        };
+        Point::from(location).manhattan(Point::from(goal)) + turns * turn_cost
+    }
+}
+
 /// Pop the next node from the queue, skipping outdated entries.
 fn pop_best<N: Copy + Eq + Hash>(
     visited: &HashMap<N, u64>,
@@ -251,3 +357,52 @@ fn pop_best_ledger<N: Copy + Eq + Hash>(
     }
     node
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Steps along a line of 0..10 cost the node being entered.
+    fn line_neighbors(n: &u64) -> Vec<(u64, u64)> {
+        [n.wrapping_sub(1), n + 1]
+            .into_iter()
+            .filter(|next| (0..10).contains(next))
+            .map(|next| (next, next))
+            .collect()
+    }
+
+    #[test]
+    fn test_astar() {
+        let (cost, path) = astar(2, line_neighbors, |&n| n.abs_diff(5), |&n| n == 5).unwrap();
+        assert_eq!((cost, path.nodes()), (12, &[2, 3, 4, 5][..]));
+        assert_eq!(dijkstra(2, line_neighbors, |&n| n == 5), Some(cost));
+
+        let (cost, path) = astar(2, line_neighbors, |_| 0, |&n| n == 2).unwrap();
+        assert_eq!((cost, path.is_empty()), (0, true));
+        assert!(astar(2, line_neighbors, |_| 0, |&n| n == 10).is_none());
+    }
+
+    #[test]
+    fn test_manhattan_with_turns() {
+        let goal = Location { row: 2, col: 2 };
+        let heuristic = manhattan_with_turns(goal, 1000);
+        assert_eq!(heuristic(&(goal, Direction::Left)), 0);
+        assert_eq!(
+            heuristic(&(Location { row: 2, col: 0 }, Direction::Right)),
+            2
+        );
+        assert_eq!(
+            heuristic(&(Location { row: 0, col: 0 }, Direction::Down)),
+            1004
+        );
+        assert_eq!(
+            heuristic(&(Location { row: 2, col: 4 }, Direction::Up)),
+            1002
+        );
+        assert_eq!(
+            heuristic(&(Location { row: 4, col: 0 }, Direction::Down)),
+            2004
+        );
+        assert_eq!(manhattan(goal)(&Location { row: 4, col: 0 }), 4);
+    }
+}
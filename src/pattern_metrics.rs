@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use crate::grid::{self, BoundingBox, GridLike, Location};
 
 // thanks Claude
 
@@ -13,23 +13,22 @@ pub struct PatternMetrics {
     total_r_count: usize,
 }
 
-impl From<&Vec<Vec<char>>> for PatternMetrics {
-    fn from(value: &Vec<Vec<char>>) -> Self {
+impl<G: GridLike<char>> From<&G> for PatternMetrics {
+    fn from(value: &G) -> Self {
         // Count total 'R's
-        let total_r_count: usize = value
-            .iter()
-            .map(|row| row.iter().filter(|&&c| c == 'R').count())
-            .sum();
+        let robots = value.find_all(|&c| c == 'R');
+        let total_r_count = robots.len();
 
         // Find bounding box
-        let bounding_box_density = calculate_bounding_box_density(value);
+        let bounding_box_density = calculate_bounding_box_density(&robots);
 
-        // Find clusters using flood fill
+        // Find clusters using connected components
         let (cluster_count, largest_cluster_size) = count_clusters(value);
 
         // Calculate row and column variances
-        let row_variance = calculate_row_variance(value);
-        let col_variance = calculate_col_variance(value);
+        let (height, width) = value.dimensions();
+        let row_variance = calculate_variance(&line_counts(&robots, height, |l| l.row));
+        let col_variance = calculate_variance(&line_counts(&robots, width, |l| l.col));
 
         PatternMetrics {
             bounding_box_density,
@@ -69,121 +68,29 @@ impl PatternMetrics {
     }
 }
 
-fn calculate_bounding_box_density(grid: &[Vec<char>]) -> f64 {
-    let height = grid.len();
-    let width = if height > 0 { grid[0].len() } else { 0 };
-
-    let mut min_row = height;
-    let mut max_row = 0;
-    let mut min_col = width;
-    let mut max_col = 0;
-    let mut r_count = 0;
-
-    for (i, row) in grid.iter().enumerate() {
-        for (j, &ch) in row.iter().enumerate() {
-            if ch == 'R' {
-                min_row = min_row.min(i);
-                max_row = max_row.max(i);
-                min_col = min_col.min(j);
-                max_col = max_col.max(j);
-                r_count += 1;
-            }
-        }
-    }
-
-    if r_count == 0 {
-        return 0.0;
-    }
-
-    let bbox_height = max_row - min_row + 1;
-    let bbox_width = max_col - min_col + 1;
-    let bbox_area = bbox_height * bbox_width;
-
-    r_count as f64 / bbox_area as f64
-}
-
-fn count_clusters(grid: &[Vec<char>]) -> (usize, usize) {
-    let height = grid.len();
-    let width = if height > 0 { grid[0].len() } else { 0 };
-
-    let mut visited = vec![vec![false; width]; height];
-    let mut cluster_count = 0;
-    let mut largest_cluster = 0;
-
-    for i in 0..height {
-        for j in 0..width {
-            if grid[i][j] == 'R' && !visited[i][j] {
-                let cluster_size = bfs_cluster(grid, &mut visited, i, j);
-                cluster_count += 1;
-                largest_cluster = largest_cluster.max(cluster_size);
-            }
-        }
+fn calculate_bounding_box_density(robots: &[Location]) -> f64 {
+    match BoundingBox::from_locations(robots.iter().copied()) {
+        Some(bbox) => robots.len() as f64 / bbox.area() as f64,
+        None => 0.0,
     }
-
-    (cluster_count, largest_cluster)
 }
 
-fn bfs_cluster(
-    grid: &[Vec<char>],
-    visited: &mut [Vec<bool>],
-    start_i: usize,
-    start_j: usize,
-) -> usize {
-    let height = grid.len();
-    let width = grid[0].len();
-    let mut queue = VecDeque::new();
-    let mut size = 0;
-
-    queue.push_back((start_i, start_j));
-    visited[start_i][start_j] = true;
-
-    while let Some((i, j)) = queue.pop_front() {
-        size += 1;
-
-        // Check 4 adjacent cells
-        let directions = [(0, 1), (1, 0), (0, -1), (-1, 0)];
-        for (di, dj) in directions.iter() {
-            let ni = i as i32 + di;
-            let nj = j as i32 + dj;
-
-            if ni >= 0 && ni < height as i32 && nj >= 0 && nj < width as i32 {
-                let ni = ni as usize;
-                let nj = nj as usize;
-
-                if grid[ni][nj] == 'R' && !visited[ni][nj] {
-                    visited[ni][nj] = true;
-                    queue.push_back((ni, nj));
-                }
-            }
-        }
-    }
-
-    size
-}
-
-fn calculate_row_variance(grid: &[Vec<char>]) -> f64 {
-    let counts: Vec<usize> = grid
-        .iter()
-        .map(|row| row.iter().filter(|&&c| c == 'R').count())
-        .collect();
+fn count_clusters(grid: &impl GridLike<char>) -> (usize, usize) {
+    let cluster_sizes: Vec<usize> =
+        grid::connected_components_where(grid, |c| c == 'R', |_, _| true)
+            .iter()
+            .map(grid::Region::area)
+            .collect();
 
-    calculate_variance(&counts)
+    let largest_cluster = cluster_sizes.iter().copied().max().unwrap_or(0);
+    (cluster_sizes.len(), largest_cluster)
 }
 
-fn calculate_col_variance(grid: &[Vec<char>]) -> f64 {
-    let height = grid.len();
-    let width = if height > 0 { grid[0].len() } else { 0 };
-
-    let mut counts = vec![0; width];
-    for row in grid.iter() {
-        for (j, &ch) in row.iter().enumerate() {
-            if ch == 'R' {
-                counts[j] += 1;
-            }
-        }
-    }
-
-    calculate_variance(&counts)
+/// Number of robots in each of `len` rows or columns, picked out by `line`.
+fn line_counts(robots: &[Location], len: usize, line: impl Fn(&Location) -> usize) -> Vec<usize> {
+    let mut counts = vec![0; len];
+    robots.iter().for_each(|r| counts[line(r)] += 1);
+    counts
 }
 
 fn calculate_variance(data: &[usize]) -> f64 {
@@ -1,7 +1,10 @@
 use std::collections::HashMap;
+use std::io;
+use std::path::Path;
 
 use crate::grid;
 use crate::pattern_metrics;
+use crate::viz;
 
 #[derive(Debug)]
 pub struct Security {
@@ -46,6 +49,15 @@ impl Security {
             .for_each(|r| r.move_location(self.num_rows, self.num_cols, time));
     }
 
+    /// Like [`Security::elapse`], but one second at a time, showing the map to
+    /// `observer` after every second.
+    pub fn elapse_observed(&mut self, time: usize, observer: &mut impl viz::Observer) {
+        for _ in 0..time {
+            self.elapse(1);
+            observer.observe_with(|| self.create_map().to_grid());
+        }
+    }
+
     pub fn get_time(&self) -> usize {
         self.time
     }
@@ -80,23 +92,31 @@ impl Security {
         let metrics = pattern_metrics::PatternMetrics::from(&map);
         let score = metrics.pattern_score();
         println!("t = {} seconds, score = {score}", self.time);
-        grid::print_map(&map);
+        map.print();
     }
 
-    fn create_map(&self) -> Vec<Vec<char>> {
-        let mut map: Vec<Vec<char>> = vec![vec!['.'; 101]; 103];
-        self.robots.iter().for_each(|r| {
-            let location = r.get_pos();
-            map[location.row][location.col] = 'R';
-        });
+    /// Save the current robot positions as a PNG, e.g. to look for the Christmas tree.
+    pub fn write_frame(&self, path: &Path) -> io::Result<()> {
+        let palette = viz::Palette::new(viz::Rgb::BLACK).with('R', viz::Rgb(0, 200, 0));
+        viz::write_png(path, &self.create_map(), &palette, 4)
+    }
+
+    /// The robots' positions on an otherwise empty map, without allocating
+    /// every cell.
+    fn create_map(&self) -> grid::SparseGrid<char> {
+        let mut map = grid::SparseGrid::with_dimensions(self.num_rows, self.num_cols, '.');
+        self.robots
+            .iter()
+            .for_each(|r| map.set(grid::Point::from(r.get_pos()), 'R'));
         map
     }
 }
 
 impl Robot {
     pub fn move_location(&mut self, num_rows: usize, num_cols: usize, time: usize) {
-        self.pos.row = Robot::get_final_dimension(self.pos.row, num_rows, self.v.row_v, time);
-        self.pos.col = Robot::get_final_dimension(self.pos.col, num_cols, self.v.col_v, time);
+        let velocity = grid::Point::new(self.v.col_v as i64, self.v.row_v as i64);
+        let target = grid::Point::from(self.pos) + velocity * time as i64;
+        self.pos = grid::wrap(target, num_rows, num_cols);
     }
 
     pub fn quadrant(&self, num_rows: usize, num_cols: usize) -> Option<i32> {
@@ -119,24 +139,6 @@ impl Robot {
             col: self.pos.col,
         }
     }
-
-    fn get_final_dimension(
-        initial: usize,
-        dimension_size: usize,
-        velocity: i32,
-        time: usize,
-    ) -> usize {
-        let offset = (time as i32 * velocity).unsigned_abs() as usize % dimension_size;
-        if velocity < 0 {
-            return initial + if offset > initial { dimension_size } else { 0 } - offset;
-        }
-        initial + offset
-            - if initial + offset >= dimension_size {
-                dimension_size
-            } else {
-                0
-            }
-    }
 }
 
 impl SecurityBuilder {
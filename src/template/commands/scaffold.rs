@@ -1,14 +1,29 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{bin_path, year_data_dir, Day, Year};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
+/// Like [`MODULE_TEMPLATE`], for solutions of a year other than the configured one.
+const YEAR_MODULE_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/src/template_year.txt"
+));
+
+/// The solution template for `day`, pinned to `year` unless that is the configured one.
+fn module_contents(day: Day, year: Option<Year>) -> String {
+    let contents = match year.filter(|&year| Some(year) != Year::current()) {
+        Some(year) => YEAR_MODULE_TEMPLATE.replace("%YEAR%", &year.to_string()),
+        None => MODULE_TEMPLATE.to_string(),
+    };
+    contents.replace("%DAY_NUMBER%", &day.into_inner().to_string())
+}
+
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
@@ -27,10 +42,18 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(day: Day, overwrite: bool, year: Option<Year>) {
+    let data_dir = year_data_dir(year);
+    let input_path = format!("{data_dir}/inputs/{day}.txt");
+    let example_path = format!("{data_dir}/examples/{day}.txt");
+    let module_path = bin_path(year, day);
+
+    if let Err(e) = fs::create_dir_all(format!("{data_dir}/inputs"))
+        .and_then(|()| fs::create_dir_all(format!("{data_dir}/examples")))
+    {
+        eprintln!("Failed to create data directories: {e}");
+        process::exit(1);
+    }
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,11 +63,7 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(module_contents(day, year).as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -75,5 +94,8 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    match year {
+        Some(year) => println!("🎄 Type `cargo solve {day} --year {year}` to run your solution."),
+        None => println!("🎄 Type `cargo solve {day}` to run your solution."),
+    }
 }
@@ -1,9 +1,16 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{bin_name, Day, Year};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    input: Option<String>,
+    year: Option<Year>,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), bin_name(year, day)];
 
     if dhat {
         cmd_args.extend([
@@ -23,8 +30,18 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
+    if let Some(input) = input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input);
+    }
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(&cmd_args);
+    if let Some(year) = year {
+        cmd.env("AOC_YEAR", year.to_string());
+    }
+
+    let mut cmd = cmd
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
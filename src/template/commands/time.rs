@@ -2,10 +2,10 @@ use std::collections::HashSet;
 
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, readme_benchmarks, Day, Year};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub fn handle(day: Option<Day>, run_all: bool, store: bool, year: Option<Year>) {
+    let stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
@@ -21,13 +21,18 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(&days_to_run, true, true, year).unwrap();
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
 
         println!();
+        // the README only lists benchmarks for the configured year.
+        if year.is_some_and(|year| Some(year) != Year::current()) {
+            println!("Stored updated benchmarks.");
+            return;
+        }
         match readme_benchmarks::update(merged_timings) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
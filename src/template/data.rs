@@ -0,0 +1,130 @@
+//! Finds puzzle data files and the input a day binary should run on.
+
+use std::error::Error;
+use std::fmt::Display;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::{env, fs};
+
+use crate::template::{Day, Year};
+
+/// Environment variable naming a data directory to use instead of `./data`.
+pub const DATA_DIR_VAR: &str = "AOC_DATA_DIR";
+
+#[derive(Debug)]
+pub enum DataError {
+    /// The file was in none of the candidate data directories.
+    NotFound { tried: Vec<PathBuf> },
+    /// `--input` was passed without a path after it.
+    MissingInputPath,
+    /// The file was found but could not be read.
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl Error for DataError {}
+
+impl Display for DataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataError::NotFound { tried } => {
+                write!(f, "could not find input file, tried:")?;
+                for path in tried {
+                    write!(f, "\n  {}", path.display())?;
+                }
+                Ok(())
+            }
+            DataError::MissingInputPath => write!(f, "--input needs a path"),
+            DataError::Io { path, source } => {
+                write!(f, "could not read {}: {source}", path.display())
+            }
+        }
+    }
+}
+
+/// Directories that may hold puzzle data, in the order they are searched:
+/// `$AOC_DATA_DIR`, then `$CARGO_MANIFEST_DIR/data`, then `./data`.
+pub fn data_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(dir) = env::var_os(DATA_DIR_VAR) {
+        dirs.push(PathBuf::from(dir));
+    }
+    // Cargo sets this when running tests and binaries; the build-time value
+    // covers binaries started directly.
+    let manifest_dir =
+        env::var_os("CARGO_MANIFEST_DIR").or(option_env!("CARGO_MANIFEST_DIR").map(Into::into));
+    if let Some(dir) = manifest_dir {
+        dirs.push(PathBuf::from(dir).join("data"));
+    }
+    if let Ok(dir) = env::current_dir() {
+        dirs.push(dir.join("data"));
+    }
+    dirs.dedup();
+    dirs
+}
+
+/// Where commands such as `scaffold` and `download` write data for `year`,
+/// defaulting to the configured one: `data/<year>`, relative to the working
+/// directory.
+pub fn year_data_dir(year: Option<Year>) -> String {
+    match year.or_else(Year::current) {
+        Some(year) => format!("data/{year}"),
+        None => "data".to_string(),
+    }
+}
+
+/// Path of the solution binary for `day` of `year`, defaulting to the
+/// configured one.
+pub fn bin_path(year: Option<Year>, day: Day) -> String {
+    format!("./src/bin/{}.rs", bin_name(year, day))
+}
+
+/// Name of the solution binary for `day` of `year`, defaulting to the
+/// configured one.
+pub fn bin_name(year: Option<Year>, day: Day) -> String {
+    match year {
+        Some(year) => year.bin_name(day),
+        None => day.to_string(),
+    }
+}
+
+/// The first existing `<data dir>/<year>/<folder>/<file_name>`, or every
+/// path tried.
+///
+/// Each data directory may also keep files for the configured year directly
+/// in `<data dir>/<folder>`, as before data was split by year.
+pub fn data_path(year: Option<Year>, folder: &str, file_name: &str) -> Result<PathBuf, DataError> {
+    let year = year.or_else(Year::current);
+    let mut tried = Vec::new();
+    for dir in data_dirs() {
+        if let Some(year) = year {
+            tried.push(dir.join(year.to_string()).join(folder).join(file_name));
+        }
+        if year.is_none() || year == Year::current() {
+            tried.push(dir.join(folder).join(file_name));
+        }
+    }
+    match tried.iter().find(|path| path.is_file()) {
+        Some(path) => Ok(path.clone()),
+        None => Err(DataError::NotFound { tried }),
+    }
+}
+
+/// The input a day binary was asked to run on: stdin for `-` (or
+/// `--input -`), the file given with `--input <path>`, or otherwise the day's
+/// file in `inputs` for `year`, defaulting to the configured one.
+pub fn read_input(year: Option<Year>, day: Day) -> Result<String, DataError> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let path = match args.iter().position(|arg| arg == "--input") {
+        Some(index) => PathBuf::from(args.get(index + 1).ok_or(DataError::MissingInputPath)?),
+        None if args.iter().any(|arg| arg == "-") => PathBuf::from("-"),
+        None => data_path(year, "inputs", &format!("{day}.txt"))?,
+    };
+
+    let read = if path.as_os_str() == "-" {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input).map(|_| input)
+    } else {
+        fs::read_to_string(&path)
+    };
+    read.map_err(|source| DataError::Io { path, source })
+}
@@ -1,57 +1,119 @@
-use std::{env, fs};
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::PathBuf;
 
 pub mod aoc_cli;
 pub mod commands;
 pub mod runner;
 
+pub use data::*;
 pub use day::*;
+pub use year::*;
 
+mod data;
 mod day;
 mod readme_benchmarks;
 mod run_multi;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
+pub const ANSI_DIM: &str = "\x1b[2m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+pub const ANSI_YELLOW: &str = "\x1b[33m";
+pub const ANSI_BLUE: &str = "\x1b[34m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file of the configured year to a string.
+///
+/// The file is looked up in each of [`data_dirs`] in turn.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
+    let f = fs::read_to_string(found_path(None, folder, &format!("{day}.txt")));
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
+    let f = fs::read_to_string(found_path(None, folder, &format!("{day}-{part}.txt")));
     f.expect("could not open input file")
 }
 
+/// Like [`read_file`], for solutions of a year other than the configured one.
+#[must_use]
+pub fn read_year_file(year: Year, folder: &str, day: Day) -> String {
+    let f = fs::read_to_string(found_path(Some(year), folder, &format!("{day}.txt")));
+    f.expect("could not open input file")
+}
+
+/// Like [`read_file_part`], for solutions of a year other than the configured one.
+#[must_use]
+pub fn read_year_file_part(year: Year, folder: &str, day: Day, part: u8) -> String {
+    let f = fs::read_to_string(found_path(Some(year), folder, &format!("{day}-{part}.txt")));
+    f.expect("could not open input file")
+}
+
+/// Helper function that reads a text file to raw bytes, skipping UTF-8 validation.
+///
+/// Pair it with [`parse_2d_bytes`](crate::input::parse_2d_bytes) for grids too
+/// large to hold as `char`s.
+#[must_use]
+pub fn read_file_bytes(folder: &str, day: Day) -> Vec<u8> {
+    let f = fs::read(found_path(None, folder, &format!("{day}.txt")));
+    f.expect("could not open input file")
+}
+
+/// Helper function that opens a text file for buffered streaming, so inputs
+/// can be parsed line by line without loading them whole.
+#[must_use]
+pub fn open_file(folder: &str, day: Day) -> BufReader<File> {
+    let f = File::open(found_path(None, folder, &format!("{day}.txt")));
+    BufReader::new(f.expect("could not open input file"))
+}
+
+fn found_path(year: Option<Year>, folder: &str, file_name: &str) -> PathBuf {
+    data_path(year, folder, file_name).unwrap_or_else(|e| panic!("{e}"))
+}
+
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Solutions for a year other than the configured one start with `year = <year>`,
+/// e.g. `solution!(year = 2023, 5)`, which also creates the constant `YEAR`.
 #[macro_export]
 macro_rules! solution {
+    (year = $year:expr, $day:expr) => {
+        $crate::solution!(@year $year);
+        $crate::solution!(@impl $day, Some(YEAR), [part_one, 1] [part_two, 2]);
+    };
+    (year = $year:expr, $day:expr, 1) => {
+        $crate::solution!(@year $year);
+        $crate::solution!(@impl $day, Some(YEAR), [part_one, 1]);
+    };
+    (year = $year:expr, $day:expr, 2) => {
+        $crate::solution!(@year $year);
+        $crate::solution!(@impl $day, Some(YEAR), [part_two, 2]);
+    };
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, None, [part_one, 1] [part_two, 2]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, None, [part_one, 1]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, None, [part_two, 2]);
+    };
+
+    (@year $year:expr) => {
+        /// The year of this solution.
+        const YEAR: $crate::template::Year = $crate::year!($year);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, $year:expr, $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -61,7 +123,7 @@ macro_rules! solution {
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
+            let input = $crate::template::read_input($year, DAY).unwrap_or_else(|e| panic!("{e}"));
             $( run_part($func, &input, DAY, $part); )*
         }
     };
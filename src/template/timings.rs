@@ -1,9 +1,7 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
-
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+use crate::template::{year_data_dir, Day, Year};
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -23,15 +21,15 @@ pub struct Timings {
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(timings_file_path(year))?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    pub fn read_from_file(year: Option<Year>) -> Self {
+        fs::read_to_string(timings_file_path(year))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
@@ -67,6 +65,10 @@ impl Timings {
     }
 }
 
+fn timings_file_path(year: Option<Year>) -> String {
+    format!("./{}/timings.json", year_data_dir(year))
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
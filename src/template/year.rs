@@ -0,0 +1,103 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::Day;
+
+/// A year Advent of Code was held in (i.e. 2015 or later).
+///
+/// Solutions for the configured year (`AOC_YEAR`) live in `src/bin/<day>.rs`,
+/// those of any other year in `src/bin/<year>-<day>.rs`, and each year's data
+/// in `data/<year>/`.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year: Year = "2023".parse().unwrap();
+/// assert_eq!(year.to_string(), "2023");
+/// assert!(Year::new(2014).is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's 2015 or later,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < 2015 {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The configured year: `AOC_YEAR` from the environment, or as it was set
+    /// when the crate was built (see `.cargo/config.toml`).
+    pub fn current() -> Option<Self> {
+        std::env::var("AOC_YEAR")
+            .ok()
+            .or(option_env!("AOC_YEAR").map(String::from))
+            .and_then(|year| year.parse().ok())
+    }
+
+    /// Name of the binary holding this year's solution for `day`.
+    pub fn bin_name(self, day: Day) -> String {
+        if Some(self) == Year::current() {
+            day.to_string()
+        } else {
+            format!("{self}-{day}")
+        }
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015,
+            concat!("invalid year `", $year, "`, expecting 2015 or later"),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
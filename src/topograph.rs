@@ -1,6 +1,7 @@
 use std::collections::HashSet;
 
 use crate::grid::{self, Location};
+use crate::{bfs, dfs};
 
 #[derive(Debug)]
 pub struct Topograph {
@@ -46,61 +47,25 @@ impl Topograph {
 
 impl Trailhead {
     pub fn score(&self, map: &[Vec<u32>]) -> u64 {
-        let mut peaks = HashSet::new();
-        Trailhead::get_peaks(map, &mut peaks, 0, self.start);
+        let reachable = bfs::flood_fill(self.start, |&location| Trailhead::uphill(map, location));
 
-        peaks.len() as u64
+        reachable
+            .into_iter()
+            .filter(|&location| grid::at(map, location) == 9)
+            .count() as u64
     }
 
     pub fn rating(&self, map: &[Vec<u32>]) -> u64 {
-        Trailhead::get_rating(map, 0, self.start)
+        dfs::count_paths(
+            self.start,
+            |&location| Trailhead::uphill(map, location),
+            |&location| grid::at(map, location) == 9,
+        )
     }
 
-    fn get_rating(map: &[Vec<u32>], altitude: u32, location: Location) -> u64 {
-        if altitude != grid::at(map, location) {
-            return 0;
-        }
-
-        if altitude == 9 {
-            return 1;
-        }
-
-        let next_altitude = altitude + 1;
-
-        crate::grid::DIRECTIONS
-            .iter()
-            .map(|&d| {
-                let next = crate::grid::get_location(map, location, d);
-                if let Some(next_location) = next {
-                    return Trailhead::get_rating(map, next_altitude, next_location);
-                }
-                0
-            })
-            .sum()
-    }
-
-    fn get_peaks(
-        map: &[Vec<u32>],
-        peaks: &mut HashSet<Location>,
-        altitude: u32,
-        location: Location,
-    ) {
-        if altitude != grid::at(map, location) {
-            return;
-        }
-
-        if altitude == 9 {
-            peaks.insert(location);
-            return;
-        }
-
-        let next_altitude = altitude + 1;
-
-        crate::grid::DIRECTIONS.iter().for_each(|&d| {
-            let next = crate::grid::get_location(map, location, d);
-            if let Some(next_location) = next {
-                Trailhead::get_peaks(map, peaks, next_altitude, next_location);
-            }
-        });
+    /// Neighbouring locations exactly one step higher than `location`.
+    fn uphill(map: &[Vec<u32>], location: Location) -> Vec<Location> {
+        let next_altitude = grid::at(map, location) + 1;
+        grid::neighbors_where(map, location, |altitude| altitude == next_altitude)
     }
 }
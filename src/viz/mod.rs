@@ -0,0 +1,298 @@
+//! Image export for character grids.
+//!
+//! Writes any [`GridLike`] map of chars, from a [`Grid`](crate::grid::Grid)
+//! to plain `Vec` rows, to PPM, PNG or SVG with one solid square per cell,
+//! coloured by a char-to-colour palette. The encoders use no compression or
+//! timestamps, so the same map always produces the same bytes. A
+//! [`Recorder`] does the same for every step of a simulation.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::grid::{GridLike, Location};
+
+pub use recorder::*;
+
+mod recorder;
+
+/// A 24-bit colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// Maps grid characters to colours, falling back to a default for unknown characters.
+#[derive(Debug, Clone)]
+pub struct Palette {
+    colors: HashMap<char, Rgb>,
+    default: Rgb,
+}
+
+impl Default for Palette {
+    /// Walls in grey, open floor in black, and everything else in white.
+    fn default() -> Self {
+        Palette::new(Rgb::WHITE)
+            .with('#', Rgb(96, 96, 96))
+            .with('.', Rgb::BLACK)
+    }
+}
+
+impl Palette {
+    pub fn new(default: Rgb) -> Self {
+        Self {
+            colors: HashMap::new(),
+            default,
+        }
+    }
+
+    pub fn with(mut self, c: char, color: Rgb) -> Self {
+        self.colors.insert(c, color);
+        self
+    }
+
+    pub fn color(&self, c: char) -> Rgb {
+        self.colors.get(&c).copied().unwrap_or(self.default)
+    }
+}
+
+/// Encode the map as a binary PPM (`P6`) image with `scale` x `scale` pixels per cell.
+pub fn to_ppm<G: GridLike<char> + ?Sized>(map: &G, palette: &Palette, scale: usize) -> Vec<u8> {
+    let (width, height) = image_size(map, scale);
+    let mut result = format!("P6\n{width} {height}\n255\n").into_bytes();
+    for row in scanlines(map, palette, scale) {
+        result.extend(row);
+    }
+    result
+}
+
+/// Encode the map as an 8-bit RGB PNG with `scale` x `scale` pixels per cell.
+pub fn to_png<G: GridLike<char> + ?Sized>(map: &G, palette: &Palette, scale: usize) -> Vec<u8> {
+    let (width, height) = image_size(map, scale);
+
+    let mut header = Vec::with_capacity(13);
+    header.extend((width as u32).to_be_bytes());
+    header.extend((height as u32).to_be_bytes());
+    // Bit depth 8, colour type 2 (RGB), default compression, filter and interlacing.
+    header.extend([8, 2, 0, 0, 0]);
+
+    // Every scanline starts with filter type 0 (none).
+    let mut raw = Vec::with_capacity(height * (width * 3 + 1));
+    for row in scanlines(map, palette, scale) {
+        raw.push(0);
+        raw.extend(row);
+    }
+
+    let mut result = b"\x89PNG\r\n\x1a\n".to_vec();
+    write_chunk(&mut result, b"IHDR", &header);
+    write_chunk(&mut result, b"IDAT", &zlib_stored(&raw));
+    write_chunk(&mut result, b"IEND", &[]);
+    result
+}
+
+/// Render the map as an SVG with one `scale`-sized square per cell.
+///
+/// Cells in the palette's default colour are covered by a single background rectangle.
+pub fn to_svg<G: GridLike<char> + ?Sized>(map: &G, palette: &Palette, scale: usize) -> String {
+    let (width, height) = image_size(map, scale);
+    let mut result = String::new();
+    writeln!(
+        result,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" shape-rendering="crispEdges">"#
+    )
+    .unwrap();
+    writeln!(
+        result,
+        r#"<rect width="{width}" height="{height}" fill="{}"/>"#,
+        palette.default.hex()
+    )
+    .unwrap();
+    for location in locations(map) {
+        let color = palette.color(map.at(location));
+        if color == palette.default {
+            continue;
+        }
+        writeln!(
+            result,
+            r#"<rect x="{}" y="{}" width="{scale}" height="{scale}" fill="{}"/>"#,
+            location.col * scale,
+            location.row * scale,
+            color.hex()
+        )
+        .unwrap();
+    }
+    result.push_str("</svg>\n");
+    result
+}
+
+pub fn write_ppm<G: GridLike<char> + ?Sized>(
+    path: &Path,
+    map: &G,
+    palette: &Palette,
+    scale: usize,
+) -> io::Result<()> {
+    fs::write(path, to_ppm(map, palette, scale))
+}
+
+pub fn write_png<G: GridLike<char> + ?Sized>(
+    path: &Path,
+    map: &G,
+    palette: &Palette,
+    scale: usize,
+) -> io::Result<()> {
+    fs::write(path, to_png(map, palette, scale))
+}
+
+pub fn write_svg<G: GridLike<char> + ?Sized>(
+    path: &Path,
+    map: &G,
+    palette: &Palette,
+    scale: usize,
+) -> io::Result<()> {
+    fs::write(path, to_svg(map, palette, scale))
+}
+
+/// Image (width, height) in pixels.
+fn image_size<G: GridLike<char> + ?Sized>(map: &G, scale: usize) -> (usize, usize) {
+    let (height, width) = map.dimensions();
+    (width * scale, height * scale)
+}
+
+/// Every location of the map's area, in row-major order.
+fn locations<G: GridLike<char> + ?Sized>(map: &G) -> impl Iterator<Item = Location> {
+    let (height, width) = map.dimensions();
+    (0..height).flat_map(move |row| (0..width).map(move |col| Location { row, col }))
+}
+
+/// RGB bytes for each row of pixels, top to bottom.
+fn scanlines<'a, G: GridLike<char> + ?Sized>(
+    map: &'a G,
+    palette: &'a Palette,
+    scale: usize,
+) -> impl Iterator<Item = Vec<u8>> + 'a {
+    let (height, width) = map.dimensions();
+    (0..height).flat_map(move |row| {
+        let line: Vec<u8> = (0..width)
+            .flat_map(|col| {
+                let Rgb(r, g, b) = palette.color(map.at(Location { row, col }));
+                [r, g, b].repeat(scale)
+            })
+            .collect();
+        std::iter::repeat_n(line, scale)
+    })
+}
+
+fn write_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+/// Wrap `data` in a zlib stream made of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = u16::MAX as usize;
+
+    let mut result = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        // An empty stream still needs one final block.
+        result.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let is_final = blocks.peek().is_none();
+        let len = block.len() as u16;
+        result.push(u8::from(is_final));
+        result.extend(len.to_le_bytes());
+        result.extend((!len).to_le_bytes());
+        result.extend(block);
+    }
+    result.extend(adler32(data).to_be_bytes());
+    result
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = u32::MAX;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + u32::from(byte)) % MOD;
+        (a, (b + a) % MOD)
+    });
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    fn sample() -> (Grid<char>, Palette) {
+        let map: Grid<char> = "#.\n.R".parse().unwrap();
+        let palette = Palette::default().with('R', Rgb(255, 0, 0));
+        (map, palette)
+    }
+
+    #[test]
+    fn test_ppm() {
+        let (map, palette) = sample();
+        let mut expected = b"P6\n2 2\n255\n".to_vec();
+        expected.extend([96, 96, 96, 0, 0, 0, 0, 0, 0, 255, 0, 0]);
+        assert_eq!(to_ppm(&map, &palette, 1), expected);
+        assert_eq!(to_ppm(&map, &palette, 3).len(), 11 + 6 * 6 * 3);
+        assert_eq!(to_ppm(&map.to_rows()[..], &palette, 1), expected);
+    }
+
+    #[test]
+    fn test_png() {
+        let (map, palette) = sample();
+        let png = to_png(&map, &palette, 2);
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], [0, 0, 0, 4, 0, 0, 0, 4]);
+        assert_eq!(&png[png.len() - 12..], b"\0\0\0\0IEND\xae\x42\x60\x82");
+        assert_eq!(png, to_png(&map, &palette, 2));
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn test_svg() {
+        let (map, palette) = sample();
+        let svg = to_svg(&map, &palette.with('.', Rgb::WHITE), 10);
+        assert_eq!(
+            svg,
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20" shape-rendering="crispEdges">"#,
+                "\n",
+                r##"<rect width="20" height="20" fill="#ffffff"/>"##,
+                "\n",
+                r##"<rect x="0" y="0" width="10" height="10" fill="#606060"/>"##,
+                "\n",
+                r##"<rect x="10" y="10" width="10" height="10" fill="#ff0000"/>"##,
+                "\n</svg>\n"
+            )
+        );
+    }
+}
@@ -0,0 +1,262 @@
+//! Frame-by-frame recording of evolving grids.
+//!
+//! Simulations report each step to an [`Observer`]; a [`Recorder`] keeps
+//! those frames and writes them out as an animated GIF or a directory of
+//! numbered PNGs.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::{image_size, to_png, Palette, Rgb};
+use crate::grid::Grid;
+
+/// Receives the state of a grid after each step of a simulation.
+///
+/// `()` ignores every step, which lets unobserved runs share the observed loop.
+pub trait Observer {
+    fn observe(&mut self, map: &Grid<char>);
+
+    /// Observe the map built by `map`, which is only called if the map is
+    /// actually used.
+    fn observe_with(&mut self, map: impl FnOnce() -> Grid<char>)
+    where
+        Self: Sized,
+    {
+        self.observe(&map());
+    }
+}
+
+impl<F: FnMut(&Grid<char>)> Observer for F {
+    fn observe(&mut self, map: &Grid<char>) {
+        self(map)
+    }
+}
+
+/// Ignores every step, without drawing any maps.
+impl Observer for () {
+    fn observe(&mut self, _map: &Grid<char>) {}
+
+    fn observe_with(&mut self, _map: impl FnOnce() -> Grid<char>) {}
+}
+
+/// Collects one frame per observed step.
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    palette: Palette,
+    scale: usize,
+    delay: u16,
+    frames: Vec<Grid<char>>,
+}
+
+impl Observer for Recorder {
+    fn observe(&mut self, map: &Grid<char>) {
+        self.frames.push(map.clone());
+    }
+}
+
+impl Recorder {
+    /// Record frames drawn with `palette`, using `scale` x `scale` pixels per cell.
+    pub fn new(palette: Palette, scale: usize) -> Self {
+        Self {
+            palette,
+            scale,
+            delay: 10,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Time each GIF frame stays on screen, in hundredths of a second.
+    pub fn delay(mut self, delay: u16) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn frames(&self) -> &[Grid<char>] {
+        &self.frames
+    }
+
+    /// Encode all frames as a looping GIF.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the frames have different dimensions, if they use more than
+    /// 256 colours, or if the image is larger than 65535 pixels on a side.
+    pub fn to_gif(&self) -> Vec<u8> {
+        let (width, height) = self
+            .frames
+            .first()
+            .map_or((0, 0), |frame| image_size(frame, self.scale));
+        let width = u16::try_from(width).expect("GIF width should fit in 16 bits");
+        let height = u16::try_from(height).expect("GIF height should fit in 16 bits");
+
+        // Number colours in order of first appearance so the output is stable.
+        let mut colors: Vec<Rgb> = Vec::new();
+        let mut indices: HashMap<char, u8> = HashMap::new();
+        for frame in &self.frames {
+            assert_eq!(
+                image_size(frame, self.scale),
+                (width as usize, height as usize),
+                "all frames should have the same dimensions"
+            );
+            for (_, &c) in frame.cells() {
+                if indices.contains_key(&c) {
+                    continue;
+                }
+                let color = self.palette.color(c);
+                let index = match colors.iter().position(|&known| known == color) {
+                    Some(index) => index,
+                    None => {
+                        colors.push(color);
+                        colors.len() - 1
+                    }
+                };
+                let index = u8::try_from(index).expect("GIF should use at most 256 colours");
+                indices.insert(c, index);
+            }
+        }
+
+        // The colour table holds a power of two entries, at least four.
+        let table_bits = colors.len().max(4).next_power_of_two().trailing_zeros() as u8;
+
+        let mut result = b"GIF89a".to_vec();
+        result.extend(width.to_le_bytes());
+        result.extend(height.to_le_bytes());
+        result.extend([0xf0 | (table_bits - 1), 0, 0]);
+        for i in 0..1 << table_bits {
+            let Rgb(r, g, b) = colors.get(i).copied().unwrap_or(Rgb::BLACK);
+            result.extend([r, g, b]);
+        }
+        // Loop forever.
+        result.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+        for frame in &self.frames {
+            let pixels: Vec<u8> = frame
+                .rows()
+                .flat_map(|row| {
+                    let line: Vec<u8> = row
+                        .iter()
+                        .flat_map(|c| std::iter::repeat_n(indices[c], self.scale))
+                        .collect();
+                    std::iter::repeat_n(line, self.scale)
+                })
+                .flatten()
+                .collect();
+
+            result.extend([0x21, 0xf9, 0x04, 0x00]);
+            result.extend(self.delay.to_le_bytes());
+            result.extend([0x00, 0x00]);
+
+            result.push(0x2c);
+            result.extend([0, 0, 0, 0]);
+            result.extend(width.to_le_bytes());
+            result.extend(height.to_le_bytes());
+            result.push(0x00);
+
+            result.push(table_bits);
+            for block in lzw_uncompressed(&pixels, table_bits).chunks(255) {
+                result.push(block.len() as u8);
+                result.extend(block);
+            }
+            result.push(0x00);
+        }
+
+        result.push(0x3b);
+        result
+    }
+
+    pub fn write_gif(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_gif())
+    }
+
+    /// Write each frame as `frame_00000.png`, `frame_00001.png`, ... into `dir`,
+    /// creating it if needed.
+    pub fn write_frames(&self, dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        for (i, frame) in self.frames.iter().enumerate() {
+            let path = dir.join(format!("frame_{i:05}.png"));
+            fs::write(path, to_png(frame, &self.palette, self.scale))?;
+        }
+        Ok(())
+    }
+}
+
+/// Encode pixels as valid GIF LZW data without actually compressing them.
+///
+/// Every pixel is written as its own literal code, and a clear code is sent
+/// before the decoder's table would grow, so the code width never changes.
+fn lzw_uncompressed(pixels: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let code_size = u32::from(min_code_size) + 1;
+    let run = usize::from(clear) - 2;
+
+    let mut writer = BitWriter::default();
+    for chunk in pixels.chunks(run) {
+        writer.write(clear, code_size);
+        for &pixel in chunk {
+            writer.write(u16::from(pixel), code_size);
+        }
+    }
+    writer.write(clear, code_size);
+    writer.write(end, code_size);
+    writer.finish()
+}
+
+/// Packs codes least significant bit first, as GIF expects.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u32) {
+        self.buffer |= u32::from(code) << self.bits;
+        self.bits += size;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recorder_gif() {
+        let mut recorder = Recorder::new(Palette::default(), 1).delay(5);
+        let mut map: Grid<char> = "#..\n.@.".parse().unwrap();
+        recorder.observe(&map);
+        map[crate::grid::Location { row: 1, col: 1 }] = '.';
+        recorder.observe(&map);
+        assert_eq!(recorder.frames().len(), 2);
+
+        let gif = recorder.to_gif();
+        assert_eq!(&gif[..6], b"GIF89a");
+        assert_eq!(&gif[6..10], [3, 0, 2, 0]);
+        // Three colours round up to a four-entry table: grey, black, white, padding.
+        assert_eq!(gif[10], 0xf1);
+        assert_eq!(&gif[13..25], [96, 96, 96, 0, 0, 0, 255, 255, 255, 0, 0, 0]);
+        assert_eq!(gif.last(), Some(&0x3b));
+        assert_eq!(gif, recorder.to_gif());
+    }
+
+    #[test]
+    fn test_lzw_uncompressed() {
+        // Clear (4), pixels 1 and 0, clear, pixel 2, clear, end (5), in 3-bit codes.
+        assert_eq!(lzw_uncompressed(&[1, 0, 2], 2), [0x0c, 0x28, 0x16]);
+    }
+}
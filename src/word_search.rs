@@ -1,3 +1,5 @@
+use crate::grid::{self, Direction8, Location, Point, DIRECTIONS8};
+
 #[derive(Debug)]
 pub struct Words {
     words: Vec<Vec<char>>,
@@ -33,67 +35,45 @@ impl Words {
         count
     }
 
-    fn count_dir(&self, row: isize, col: isize, dr: isize, dc: isize) -> bool {
-        let rows = self.words.len() as isize;
-        let cols = self.words[0].len() as isize;
-
-        if (0..4).any(|i| {
-            let r = row + dr * i;
-            let c = col + dc * i;
-            r < 0 || c < 0 || r >= rows || c >= cols
-        }) {
-            return false;
-        }
-
-        let seq: Vec<char> = (0..4)
-            .map(|i| self.words[(row + dr * i) as usize][(col + dc * i) as usize])
-            .collect();
-
-        seq == ['X', 'M', 'A', 'S']
+    /// Whether the cells on `ray` spell out exactly `word`.
+    fn spells(&self, ray: grid::Ray, word: &str) -> bool {
+        ray.take(word.len())
+            .map(|l| self.words[l.row][l.col])
+            .eq(word.chars())
     }
 
-    fn count_xs(&self, row: isize, col: isize, slope: isize) -> bool {
-        let rows = self.words.len() as isize;
-        let cols = self.words[0].len() as isize;
-
-        if (-1..=1).any(|i| {
-            let r = row + slope * i;
-            let c = col + i;
-            r < 0 || c < 0 || r >= rows || c >= cols
-        }) {
-            return false;
-        }
+    fn ray(&self, from: Point, direction: Direction8) -> grid::Ray {
+        grid::ray(
+            from,
+            Point::from(direction),
+            self.words.len(),
+            self.words[0].len(),
+        )
+    }
 
-        let seq: Vec<char> = (-1..=1)
-            .map(|i| self.words[(row + slope * i) as usize][(col + i) as usize])
-            .collect();
+    fn count_dir(&self, location: Location, direction: Direction8) -> bool {
+        self.spells(self.ray(Point::from(location), direction), "XMAS")
+    }
 
-        seq == ['M', 'A', 'S'] || seq == ['S', 'A', 'M']
+    fn count_xs(&self, location: Location, diagonal: Direction8) -> bool {
+        let start = Point::from(location) - Point::from(diagonal);
+        let ray = self.ray(start, diagonal);
+        self.spells(ray.clone(), "MAS") || self.spells(ray, "SAM")
     }
 
     fn count_xmas(&self, row: usize, col: usize) -> u64 {
-        let dirs = [
-            (0, 1),
-            (0, -1),
-            (1, 0),
-            (-1, 0),
-            (1, 1),
-            (1, -1),
-            (-1, 1),
-            (-1, -1),
-        ];
-
-        dirs.iter()
-            .filter(|(dr, dc)| self.count_dir(row as isize, col as isize, *dr, *dc))
+        DIRECTIONS8
+            .iter()
+            .filter(|&&d| self.count_dir(Location { row, col }, d))
             .count() as u64
     }
 
     fn check_x_shape(&self, row: usize, col: usize) -> bool {
-        let slopes = [1, -1];
+        let diagonals = [Direction8::DownRight, Direction8::UpRight];
 
-        slopes
+        diagonals
             .into_iter()
-            .filter(|&slope| self.count_xs(row as isize, col as isize, slope))
+            .filter(|&diagonal| self.count_xs(Location { row, col }, diagonal))
             .count()
             == 2
     }